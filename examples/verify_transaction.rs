@@ -1,5 +1,8 @@
 use anyhow::{anyhow, Result};
-use solana_client_sdk::bridge_client::BitvmBridgeClient;
+use solana_client_sdk::{
+    bridge_client::BitvmBridgeClient,
+    btc::{hash_from_hex, merkle::MerkleBranch, transaction::BtcTransaction},
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -30,32 +33,46 @@ async fn main() -> Result<()> {
     let block_header_hex = "00000020ac4051ad1135646ec3b65ab93bfcb42623f10f2861181b46043c0000000000002040291045b1a55e6db13db7dbd8c3147bf2d8f583b5381b0293bd62c7532f632666c067ffff001d266f87e3";
     let block_header = hex::decode(block_header_hex)?;
 
-    // Transaction ID (32 bytes)
-    let tx_id_hex = "444fc273af34a87424a4b72c10b6393b0353505049128c5cb383da0358ab0b58";
-    let mut tx_id: [u8; 32] = hex::decode(tx_id_hex)?
-        .try_into()
-        .map_err(|_| anyhow!("Invalid tx_id length"))?;
-    tx_id.reverse();
+    // Raw transaction data
+    let raw_tx_hex = "020000000196a29590d526af588a13025d69cd9549b9c4712e212c7e51231166936bc0717c0100000000ffffffff0220a10700000000002200204ee81665c8c767c0dfe02e6d7c1b446ab01e84dc29b4c3634bc5daae24b52cbdca906d3500000000225120418ac1703f758fe750fecd897aac19c65cf41aeb58520b564316b3c02051305b00000000";
+    let tx = BtcTransaction::from_hex(raw_tx_hex)?;
+    let raw_tx = tx.raw_tx();
+
+    // Transaction ID (32 bytes, internal byte order)
+    let tx_id_hex = tx.tx_id_hex();
+    let tx_id = tx.tx_id();
 
     // Transaction index in the block
     let tx_index = 7;
 
-    // Merkle proof (each element is 32 bytes)
-    let merkle_proof_hex = "7c71c06b93661123517e2c212e71c4b94995cd695d02138a58af26d59095a29639e334b03828f792713b73abf3d3ed0c7202224e390f8d2f6b93f22dca85ea0b81c84af181d04680ade41b5dc3006876a3bf9fe4bf21193ffef1f0124e461ecd5c796b6c9707e804882e136418bcce72f005f59f8fa1bea691bf3738e77ab27830ecfe6fab2e947c9e452b53fc29773bb6e2f9d2c68c494b3d8f34ecd1b3dde4cf735b17a31639e35b885201a60d955aa1a83b057ba5fc59e6c5c27d2f90752da6c10f67250e44003cf0f554fa71cfa5e1aa4a6c1b676f422474dfd185afe378d2efbbda3e25ff85145d37b6fb3c51cfc9e3aedeca7ffe87e500cff2d55944acf1fb3c918c3fb6ced073e824323d02c78533780f15c49ce124dcc69f31b7260b2fd97ba6b3aed1a550db3e5418804fb8ce812b23e10fed70a65c29907c1ec9f7108d669f898dc0926043d8314d199e89f1113463e57997b7fe86212dbb735935112d761bb89f9e69ecd0d1471032d964847fc3a0318d07ce36f09e6537a3011e";
-    let merkle_bytes = hex::decode(merkle_proof_hex)?;
-    let mut merkle_path = Vec::new();
-    for chunk in merkle_bytes.chunks(32) {
-        if chunk.len() == 32 {
-            let mut hash: [u8; 32] = chunk.try_into().unwrap();
-            hash.reverse();
-            merkle_path.push(hash);
-        }
+    // Merkle proof, sibling hashes from the leaf up as shown by block explorers
+    let merkle_proof_hex = [
+        "7c71c06b93661123517e2c212e71c4b94995cd695d02138a58af26d59095a296",
+        "39e334b03828f792713b73abf3d3ed0c7202224e390f8d2f6b93f22dca85ea0b",
+        "81c84af181d04680ade41b5dc3006876a3bf9fe4bf21193ffef1f0124e461ecd",
+        "5c796b6c9707e804882e136418bcce72f005f59f8fa1bea691bf3738e77ab278",
+        "30ecfe6fab2e947c9e452b53fc29773bb6e2f9d2c68c494b3d8f34ecd1b3dde4",
+        "cf735b17a31639e35b885201a60d955aa1a83b057ba5fc59e6c5c27d2f90752d",
+        "a6c10f67250e44003cf0f554fa71cfa5e1aa4a6c1b676f422474dfd185afe378",
+        "d2efbbda3e25ff85145d37b6fb3c51cfc9e3aedeca7ffe87e500cff2d55944ac",
+        "f1fb3c918c3fb6ced073e824323d02c78533780f15c49ce124dcc69f31b7260b",
+        "2fd97ba6b3aed1a550db3e5418804fb8ce812b23e10fed70a65c29907c1ec9f7",
+        "108d669f898dc0926043d8314d199e89f1113463e57997b7fe86212dbb735935",
+        "112d761bb89f9e69ecd0d1471032d964847fc3a0318d07ce36f09e6537a3011e",
+    ];
+    let branch = MerkleBranch {
+        tx_index,
+        hashes: merkle_proof_hex
+            .iter()
+            .map(|hash| hash_from_hex(hash))
+            .collect::<Result<_, _>>()?,
+    };
+
+    // Make sure the branch leads to the merkle root of the block header
+    if !branch.verify(&tx_id, &block_header)? {
+        return Err(anyhow!("Merkle proof does not match the block header"));
     }
 
-    // Raw transaction data
-    let raw_tx_hex = "020000000196a29590d526af588a13025d69cd9549b9c4712e212c7e51231166936bc0717c0100000000ffffffff0220a10700000000002200204ee81665c8c767c0dfe02e6d7c1b446ab01e84dc29b4c3634bc5daae24b52cbdca906d3500000000225120418ac1703f758fe750fecd897aac19c65cf41aeb58520b564316b3c02051305b00000000";
-    let raw_tx = hex::decode(raw_tx_hex)?;
-
     // Output index
     let output_index = 0;
 
@@ -69,6 +86,9 @@ async fn main() -> Result<()> {
         .try_into()
         .map_err(|_| anyhow!("Invalid expected_script_hash length"))?;
 
+    // Make sure the output pays the expected amount to the expected script
+    tx.check_output(output_index, expected_amount, &expected_script_hash)?;
+
     println!("Verifying Bitcoin transaction: {}", tx_id_hex);
     println!("Block height: {}", block_height);
     println!("Transaction index: {}", tx_index);
@@ -79,8 +99,8 @@ async fn main() -> Result<()> {
             block_height,
             &block_header,
             tx_id,
-            tx_index,
-            merkle_path,
+            branch.tx_index,
+            branch.hashes,
             &raw_tx,
             output_index,
             expected_amount,
@@ -97,21 +117,19 @@ async fn main() -> Result<()> {
 
     // Check verification status
     let verification_status = client.get_tx_verification_status(tx_id).await?;
-    println!(
-        "Transaction verification status: {}",
-        if verification_status {
-            "Verified"
-        } else {
-            "Not verified"
-        }
-    );
+    println!("Transaction verification status: {:?}", verification_status);
 
     // If transaction is not verified, poll for status
-    if !verification_status {
+    if !verification_status.allows_mint() {
         println!("Transaction not yet verified. Polling for status...");
 
         let mut retries = 5;
-        while retries > 0 && !client.get_tx_verification_status(tx_id).await? {
+        while retries > 0
+            && !client
+                .get_tx_verification_status(tx_id)
+                .await?
+                .allows_mint()
+        {
             println!(
                 "Still waiting for verification... {} attempts left",
                 retries
@@ -121,14 +139,7 @@ async fn main() -> Result<()> {
         }
 
         let final_status = client.get_tx_verification_status(tx_id).await?;
-        println!(
-            "Final verification status: {}",
-            if final_status {
-                "Verified"
-            } else {
-                "Not verified"
-            }
-        );
+        println!("Final verification status: {:?}", final_status);
     }
 
     Ok(())
@@ -1,200 +1,1309 @@
 use anchor_client::{
+    anchor_lang::AccountDeserialize,
+    solana_client::{
+        client_error::ClientError,
+        nonblocking::{nonce_utils, rpc_client::RpcClient},
+        rpc_config::RpcSimulateTransactionConfig,
+        rpc_request::MAX_MULTIPLE_ACCOUNTS,
+    },
     solana_sdk::{
         commitment_config::CommitmentConfig,
-        compute_budget::ComputeBudgetInstruction,
+        hash::Hash,
+        instruction::Instruction,
+        message::Message,
+        nonce::{state::Data as NonceData, State as NonceState},
         pubkey::Pubkey,
-        signature::{Keypair, Signer},
-        system_program,
+        signature::{read_keypair_file, Keypair, Signature, Signer},
+        system_instruction,
+        transaction::{Transaction, TransactionError, VersionedTransaction},
     },
-    Client, Cluster, Program,
+    Cluster,
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::TokenAccount};
+use bitvm_bridge::state::{BridgeState, TxMintedState};
+use btc_light_client::{
+    instructions::verify_tx::BtcTxProof,
+    state::{BlockHashEntry, BtcLightClientState, TxVerifiedState},
 };
-use anchor_spl::{
-    associated_token::{get_associated_token_address, spl_associated_token_account},
-    token::spl_token,
+use std::{
+    ops::RangeInclusive,
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    btc::{address::parse_btc_address, header::BlockHeader, Network},
+    compute_budget::{
+        compute_unit_price_from_recent_fees, writable_accounts, ComputeBudgetConfig, PriorityFee,
+        VERIFY_TX_COMPUTE_UNIT_LIMIT,
+    },
+    error::{BridgeError, Result},
+    instructions, pda, preflight,
+    query_client::QueryClient,
+    simulation::SimulationResult,
+    state::{
+        BlockHashEntrySnapshot, BridgeStateSnapshot, BtcLightClientStateSnapshot,
+        TxMintedStateSnapshot, TxVerificationStatus,
+    },
 };
 
-use bitvm_bridge::{accounts, instruction as bridge_instruction, state::BridgeState};
-use btc_light_client::state::BtcLightClientState;
-use std::{str::FromStr, sync::Arc};
+/// Default timeout applied to every RPC request issued by the client
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(30);
 
-use crate::query_client::QueryClient;
+/// Interval between light client polls in [`BitvmBridgeClient::wait_for_confirmations`]
+pub const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(10);
 
-pub struct BitvmBridgeClient {
+/// Maximum number of heights fetched by [`BitvmBridgeClient::get_block_hash_entries`]
+pub const MAX_BLOCK_HASH_ENTRIES: u64 = 1_000;
+
+/// Client for the BitVM bridge and BTC light client programs
+///
+/// The client is generic over the signer paying for (and authorizing) bridge transactions, so
+/// any [`Signer`] can be plugged in: an in-memory [`Keypair`], a remote KMS/HSM signer, or a
+/// boxed `Box<dyn Signer + Send + Sync>` chosen at runtime.
+pub struct BitvmBridgeClient<S = Keypair> {
     query_client: QueryClient,
-    payer: Arc<Keypair>,
-    bitvm_bridge_program: Program<Arc<Keypair>>,
-    btc_light_client_program: Program<Arc<Keypair>>,
+    rpc_client: RpcClient,
+    cluster: Cluster,
+    payer: Arc<S>,
+    bitvm_bridge_program_id: Pubkey,
+    btc_light_client_program_id: Pubkey,
+    compute_budget: ComputeBudgetConfig,
+    bridge_state: (Pubkey, u8),
+    btc_light_client_state: (Pubkey, u8),
+    btc_network: Option<Network>,
+}
+
+/// Per-call overrides for bridge transactions
+#[derive(Clone, Copy, Default)]
+pub struct TransactionOptions<'a> {
+    /// Signer paying the transaction fees, defaults to the client payer
+    pub fee_payer: Option<&'a (dyn Signer + Sync)>,
+    /// Mint authority for mints and token owner for burns, defaults to the client payer
+    pub authority: Option<&'a (dyn Signer + Sync)>,
+    /// Compute budget for this call, defaults to the client compute budget
+    pub compute_budget: Option<ComputeBudgetConfig>,
+    /// Validate the call against on-chain state before signing, see [`crate::preflight`]
+    ///
+    /// Burns are checked unless this is `Some(false)`, mints only when it is `Some(true)`.
+    pub preflight: Option<bool>,
+}
+
+impl<'a> TransactionOptions<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fee_payer(mut self, fee_payer: &'a (dyn Signer + Sync)) -> Self {
+        self.fee_payer = Some(fee_payer);
+        self
+    }
+
+    pub fn authority(mut self, authority: &'a (dyn Signer + Sync)) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    pub fn compute_budget(mut self, compute_budget: ComputeBudgetConfig) -> Self {
+        self.compute_budget = Some(compute_budget);
+        self
+    }
+
+    pub fn preflight(mut self, preflight: bool) -> Self {
+        self.preflight = Some(preflight);
+        self
+    }
+}
+
+/// Accounts and blockhash of a transaction built for offline signing
+///
+/// Signers are given as public keys only, the transaction is returned unsigned.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnsignedTransactionOptions {
+    /// Account paying the transaction fees, defaults to the client payer
+    pub fee_payer: Option<Pubkey>,
+    /// Mint authority for mints and token owner for burns, defaults to the client payer
+    pub authority: Option<Pubkey>,
+    /// Compute budget for this call, defaults to the client compute budget
+    pub compute_budget: Option<ComputeBudgetConfig>,
+    /// Recent blockhash, fetched from the cluster when not set
+    ///
+    /// With a durable nonce this is the stored nonce, fetched from the nonce account when not set.
+    pub recent_blockhash: Option<Hash>,
+    /// Durable nonce used instead of a recent blockhash
+    pub nonce: Option<DurableNonce>,
+    /// Validate the call against on-chain state before building, see [`crate::preflight`]
+    ///
+    /// Burns are checked unless this is `Some(false)`, mints only when it is `Some(true)`.
+    pub preflight: Option<bool>,
+}
+
+/// Durable nonce account advanced by a transaction
+///
+/// Transactions built against a nonce stay valid until the nonce is advanced, which gives
+/// offline and multi-party signers more time than a blockhash lifetime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurableNonce {
+    /// Nonce account
+    pub account: Pubkey,
+    /// Authority of the nonce account, must sign the transaction
+    pub authority: Pubkey,
+}
+
+impl UnsignedTransactionOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fee_payer(mut self, fee_payer: Pubkey) -> Self {
+        self.fee_payer = Some(fee_payer);
+        self
+    }
+
+    pub fn authority(mut self, authority: Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    pub fn compute_budget(mut self, compute_budget: ComputeBudgetConfig) -> Self {
+        self.compute_budget = Some(compute_budget);
+        self
+    }
+
+    pub fn recent_blockhash(mut self, recent_blockhash: Hash) -> Self {
+        self.recent_blockhash = Some(recent_blockhash);
+        self
+    }
+
+    pub fn nonce(mut self, account: Pubkey, authority: Pubkey) -> Self {
+        self.nonce = Some(DurableNonce { account, authority });
+        self
+    }
+
+    pub fn preflight(mut self, preflight: bool) -> Self {
+        self.preflight = Some(preflight);
+        self
+    }
 }
 
-impl BitvmBridgeClient {
+/// Builder for [`BitvmBridgeClient`]
+///
+/// Program ids default to the ids declared by the `bitvm_bridge` and `btc_light_client`
+/// crates, the cluster defaults to localnet and the commitment to `confirmed`.
+pub struct BitvmBridgeClientBuilder<S = Keypair> {
+    cluster: Cluster,
+    http_url: Option<String>,
+    ws_url: Option<String>,
+    commitment: CommitmentConfig,
+    timeout: Duration,
+    bitvm_bridge_program_id: Pubkey,
+    btc_light_client_program_id: Pubkey,
+    payer: Option<Arc<S>>,
+    compute_budget: ComputeBudgetConfig,
+    btc_network: Option<Network>,
+}
+
+impl<S> Default for BitvmBridgeClientBuilder<S> {
+    fn default() -> Self {
+        Self {
+            cluster: Cluster::Localnet,
+            http_url: None,
+            ws_url: None,
+            commitment: CommitmentConfig::confirmed(),
+            timeout: DEFAULT_RPC_TIMEOUT,
+            bitvm_bridge_program_id: bitvm_bridge::ID,
+            btc_light_client_program_id: btc_light_client::ID,
+            payer: None,
+            compute_budget: ComputeBudgetConfig::default(),
+            btc_network: None,
+        }
+    }
+}
+
+impl<S: Signer + Send + Sync> BitvmBridgeClientBuilder<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a named cluster (mainnet, devnet, localnet, ...)
+    pub fn cluster(mut self, cluster: Cluster) -> Self {
+        self.cluster = cluster;
+        self
+    }
+
+    /// Override the HTTP RPC endpoint of the configured cluster
+    pub fn url(mut self, http_url: &str) -> Self {
+        self.http_url = Some(http_url.to_string());
+        self
+    }
+
+    /// Override the websocket endpoint of the configured cluster
+    pub fn ws_url(mut self, ws_url: &str) -> Self {
+        self.ws_url = Some(ws_url.to_string());
+        self
+    }
+
+    pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Timeout applied to each RPC request
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn bitvm_bridge_program_id(mut self, program_id: Pubkey) -> Self {
+        self.bitvm_bridge_program_id = program_id;
+        self
+    }
+
+    pub fn btc_light_client_program_id(mut self, program_id: Pubkey) -> Self {
+        self.btc_light_client_program_id = program_id;
+        self
+    }
+
+    /// Signer used as fee payer and authority of bridge transactions
+    pub fn payer(mut self, payer: S) -> Self {
+        self.payer = Some(Arc::new(payer));
+        self
+    }
+
+    /// Same as [`Self::payer`] for a signer shared with the rest of the application
+    pub fn payer_arc(mut self, payer: Arc<S>) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Default compute budget prepended to every bridge transaction
+    pub fn compute_budget(mut self, compute_budget: ComputeBudgetConfig) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+
+    pub fn compute_unit_limit(mut self, compute_unit_limit: u32) -> Self {
+        self.compute_budget.compute_unit_limit = Some(compute_unit_limit);
+        self
+    }
+
+    pub fn compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_budget.priority_fee = PriorityFee::Fixed(micro_lamports);
+        self
+    }
+
+    pub fn priority_fee(mut self, priority_fee: PriorityFee) -> Self {
+        self.compute_budget.priority_fee = priority_fee;
+        self
+    }
+
+    /// Bitcoin network burn addresses must belong to, any network is accepted when unset
+    pub fn btc_network(mut self, btc_network: Network) -> Self {
+        self.btc_network = Some(btc_network);
+        self
+    }
+
+    fn resolve_cluster(&self) -> Cluster {
+        if self.http_url.is_none() && self.ws_url.is_none() {
+            return self.cluster.clone();
+        }
+
+        let http_url = self
+            .http_url
+            .clone()
+            .unwrap_or_else(|| self.cluster.url().to_string());
+        let ws_url = match &self.ws_url {
+            Some(ws_url) => ws_url.clone(),
+            None if self.http_url.is_some() => Cluster::from_str(&http_url)
+                .map(|cluster| cluster.ws_url().to_string())
+                .unwrap_or_else(|_| http_url.clone()),
+            None => self.cluster.ws_url().to_string(),
+        };
+        Cluster::Custom(http_url, ws_url)
+    }
+
+    pub fn build(self) -> Result<BitvmBridgeClient<S>> {
+        let payer = self
+            .payer
+            .clone()
+            .ok_or_else(|| BridgeError::InvalidConfig("payer is required".to_string()))?;
+        let cluster = self.resolve_cluster();
+        let url = cluster.url().to_string();
+
+        let rpc_client =
+            RpcClient::new_with_timeout_and_commitment(url.clone(), self.timeout, self.commitment);
+        let query_client =
+            QueryClient::new_with_timeout_and_commitment(url, self.timeout, self.commitment)?;
+
+        Ok(BitvmBridgeClient {
+            query_client,
+            rpc_client,
+            cluster,
+            payer,
+            bitvm_bridge_program_id: self.bitvm_bridge_program_id,
+            btc_light_client_program_id: self.btc_light_client_program_id,
+            compute_budget: self.compute_budget,
+            bridge_state: pda::bridge_state(&self.bitvm_bridge_program_id),
+            btc_light_client_state: pda::btc_light_client_state(&self.btc_light_client_program_id),
+            btc_network: self.btc_network,
+        })
+    }
+}
+
+impl BitvmBridgeClientBuilder<Keypair> {
+    /// Use a base58 encoded private key as payer
+    pub fn private_key(self, private_key: &str) -> Result<Self> {
+        Ok(self.payer(keypair_from_base58(private_key)?))
+    }
+
+    /// Use a Solana CLI JSON keypair file as payer
+    pub fn keypair_file<P: AsRef<Path>>(self, path: P) -> Result<Self> {
+        Ok(self.payer(keypair_from_file(path)?))
+    }
+}
+
+/// Heights of `block_heights` in order, rejecting ranges over [`MAX_BLOCK_HASH_ENTRIES`]
+fn block_height_range(block_heights: RangeInclusive<u64>) -> Result<Vec<u64>> {
+    let (start, end) = (*block_heights.start(), *block_heights.end());
+    if end >= start && end - start >= MAX_BLOCK_HASH_ENTRIES {
+        return Err(BridgeError::InvalidArgument(format!(
+            "block height range {}..={} spans more than {} heights",
+            start, end, MAX_BLOCK_HASH_ENTRIES
+        )));
+    }
+    Ok(block_heights.collect())
+}
+
+/// Drop repeated keys from `signers`, keeping the first occurrence
+///
+/// The same key may act as fee payer and authority, it is only asked to sign once.
+fn unique_signers<'a>(signers: &[&'a (dyn Signer + Sync)]) -> Vec<&'a (dyn Signer + Sync)> {
+    let mut unique_signers: Vec<&(dyn Signer + Sync)> = Vec::with_capacity(signers.len());
+    for signer in signers {
+        if !unique_signers.iter().any(|s| s.pubkey() == signer.pubkey()) {
+            unique_signers.push(*signer);
+        }
+    }
+    unique_signers
+}
+
+/// Decode a base58 encoded private key
+pub fn keypair_from_base58(private_key: &str) -> Result<Keypair> {
+    let private_key = bs58::decode(private_key).into_vec()?;
+    Keypair::from_bytes(&private_key).map_err(|e| BridgeError::InvalidKeypair(e.to_string()))
+}
+
+/// Load a keypair from a Solana CLI JSON keypair file (e.g. `~/.config/solana/id.json`)
+pub fn keypair_from_file<P: AsRef<Path>>(path: P) -> Result<Keypair> {
+    let path = path.as_ref();
+    read_keypair_file(path)
+        .map_err(|e| BridgeError::InvalidKeypair(format!("{}: {}", path.display(), e)))
+}
+
+impl BitvmBridgeClient<Keypair> {
     pub fn new(
         url: &str,
         bitvm_bridge_contract: &str,
         btc_light_client_contract: &str,
         private_key: &str,
-    ) -> anyhow::Result<Self> {
-        let private_key = bs58::decode(private_key).into_vec()?;
-        let payer = Keypair::from_bytes(&private_key)?;
-        let payer = Arc::new(payer);
-        let cluster = Cluster::Custom(url.to_string(), url.to_string());
+    ) -> Result<Self> {
+        Self::new_with_signer(
+            url,
+            bitvm_bridge_contract,
+            btc_light_client_contract,
+            keypair_from_base58(private_key)?,
+        )
+    }
 
-        let client =
-            Client::new_with_options(cluster, payer.clone(), CommitmentConfig::confirmed());
+    /// Create a client paying with the keypair stored in a Solana CLI JSON keypair file
+    pub fn new_with_keypair_file<P: AsRef<Path>>(
+        url: &str,
+        bitvm_bridge_contract: &str,
+        btc_light_client_contract: &str,
+        keypair_path: P,
+    ) -> Result<Self> {
+        Self::new_with_signer(
+            url,
+            bitvm_bridge_contract,
+            btc_light_client_contract,
+            keypair_from_file(keypair_path)?,
+        )
+    }
 
-        let bitvm_bridge_program = client.program(Pubkey::from_str(bitvm_bridge_contract)?)?;
-        let btc_light_client_program =
-            client.program(Pubkey::from_str(btc_light_client_contract)?)?;
+    // Associated validators live in this non-generic impl so that
+    // `BitvmBridgeClient::validate_*` resolves without naming a signer type
+    pub fn validate_solana_address(address: &str) -> Result<()> {
+        Pubkey::from_str(address)?;
+        Ok(())
+    }
 
-        let query_client = QueryClient::new(url.to_string())?;
+    /// Check that `address` is a P2PKH, P2SH, P2WPKH, P2WSH or P2TR address of `network`
+    pub fn validate_btc_address(address: &str, network: Network) -> Result<()> {
+        parse_btc_address(address, Some(network))?;
+        Ok(())
+    }
+}
 
-        Ok(Self {
-            query_client,
-            payer,
-            bitvm_bridge_program,
-            btc_light_client_program,
-        })
+impl<S: Signer + Send + Sync> BitvmBridgeClient<S> {
+    /// Create a client paying with an arbitrary signer
+    pub fn new_with_signer(
+        url: &str,
+        bitvm_bridge_contract: &str,
+        btc_light_client_contract: &str,
+        signer: S,
+    ) -> Result<Self> {
+        Self::builder()
+            .url(url)
+            .ws_url(url)
+            .bitvm_bridge_program_id(Pubkey::from_str(bitvm_bridge_contract)?)
+            .btc_light_client_program_id(Pubkey::from_str(btc_light_client_contract)?)
+            .payer(signer)
+            .build()
     }
 
-    pub async fn mint_tokens(
+    pub fn builder() -> BitvmBridgeClientBuilder<S> {
+        BitvmBridgeClientBuilder::new()
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    pub fn cluster(&self) -> &Cluster {
+        &self.cluster
+    }
+
+    pub fn commitment(&self) -> CommitmentConfig {
+        self.rpc_client.commitment()
+    }
+
+    pub fn compute_budget(&self) -> ComputeBudgetConfig {
+        self.compute_budget
+    }
+
+    pub fn bitvm_bridge_program_id(&self) -> Pubkey {
+        self.bitvm_bridge_program_id
+    }
+
+    pub fn btc_light_client_program_id(&self) -> Pubkey {
+        self.btc_light_client_program_id
+    }
+
+    /// Bridge state address and bump, derived once at construction
+    pub fn bridge_state_pda(&self) -> (Pubkey, u8) {
+        self.bridge_state
+    }
+
+    /// Light client state address and bump, derived once at construction
+    pub fn btc_light_client_state_pda(&self) -> (Pubkey, u8) {
+        self.btc_light_client_state
+    }
+
+    /// Bitcoin network burn addresses are checked against, `None` accepts any network
+    pub fn btc_network(&self) -> Option<Network> {
+        self.btc_network
+    }
+
+    /// Fetch and deserialize an Anchor account through the client's RPC connection
+    async fn account<T: AccountDeserialize>(&self, address: Pubkey) -> Result<T> {
+        self.optional_account(address)
+            .await?
+            .ok_or(BridgeError::AccountNotFound(address))
+    }
+
+    /// Fetch and deserialize an Anchor account, `None` if it does not exist
+    async fn optional_account<T: AccountDeserialize>(&self, address: Pubkey) -> Result<Option<T>> {
+        self.rpc_client
+            .get_account_with_commitment(&address, self.commitment())
+            .await?
+            .value
+            .map(|account| Ok(T::try_deserialize(&mut account.data.as_slice())?))
+            .transpose()
+    }
+
+    /// Fetch and deserialize Anchor accounts with `getMultipleAccounts`, in batches of
+    /// [`MAX_MULTIPLE_ACCOUNTS`]
+    async fn multiple_accounts<T: AccountDeserialize>(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<T>>> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let chunk_accounts = self
+                .rpc_client
+                .get_multiple_accounts_with_commitment(chunk, self.commitment())
+                .await?
+                .value;
+            for account in chunk_accounts {
+                accounts.push(
+                    account
+                        .map(|account| T::try_deserialize(&mut account.data.as_slice()))
+                        .transpose()?,
+                );
+            }
+        }
+        Ok(accounts)
+    }
+
+    /// Resolve the fee payer and authority of a call, falling back to the client payer
+    fn signers<'a>(
+        &'a self,
+        options: &TransactionOptions<'a>,
+    ) -> (&'a (dyn Signer + Sync), &'a (dyn Signer + Sync)) {
+        let payer: &'a (dyn Signer + Sync) = &*self.payer;
+        (
+            options.fee_payer.unwrap_or(payer),
+            options.authority.unwrap_or(payer),
+        )
+    }
+
+    /// Estimate a compute unit price from `getRecentPrioritizationFees` for `accounts`
+    pub async fn estimate_compute_unit_price(
+        &self,
+        accounts: &[Pubkey],
+        percentile: u8,
+        max_price: u64,
+    ) -> Result<u64> {
+        let fees = self
+            .rpc_client
+            .get_recent_prioritization_fees(accounts)
+            .await?
+            .iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect::<Vec<_>>();
+        Ok(compute_unit_price_from_recent_fees(
+            &fees, percentile, max_price,
+        ))
+    }
+
+    /// Compute budget instructions for `instructions`, resolving automatic priority fees
+    async fn compute_budget_instructions(
+        &self,
+        compute_budget: Option<ComputeBudgetConfig>,
+        default_compute_unit_limit: Option<u32>,
+        instructions: &[Instruction],
+    ) -> Result<Vec<Instruction>> {
+        let compute_budget = compute_budget.unwrap_or(self.compute_budget);
+        let compute_unit_price = match compute_budget.priority_fee {
+            PriorityFee::None => None,
+            PriorityFee::Fixed(price) => Some(price),
+            PriorityFee::Auto {
+                percentile,
+                max_price,
+            } => Some(
+                self.estimate_compute_unit_price(
+                    &writable_accounts(instructions),
+                    percentile,
+                    max_price,
+                )
+                .await?,
+            ),
+        };
+        Ok(compute_budget.instructions(default_compute_unit_limit, compute_unit_price))
+    }
+
+    /// Prepend the compute budget instructions to `instructions`
+    async fn with_compute_budget(
+        &self,
+        instructions: Vec<Instruction>,
+        compute_budget: Option<ComputeBudgetConfig>,
+        default_compute_unit_limit: Option<u32>,
+    ) -> Result<Vec<Instruction>> {
+        let mut all_instructions = self
+            .compute_budget_instructions(compute_budget, default_compute_unit_limit, &instructions)
+            .await?;
+        all_instructions.extend(instructions);
+        Ok(all_instructions)
+    }
+
+    /// Sign `instructions` and send them, prepending the compute budget
+    ///
+    /// The first signer pays the transaction fees.
+    async fn send_instructions(
+        &self,
+        instructions: Vec<Instruction>,
+        compute_budget: Option<ComputeBudgetConfig>,
+        default_compute_unit_limit: Option<u32>,
+        signers: &[&(dyn Signer + Sync)],
+    ) -> Result<Signature> {
+        let all_instructions = self
+            .with_compute_budget(instructions, compute_budget, default_compute_unit_limit)
+            .await?;
+
+        let fee_payer = signers
+            .first()
+            .ok_or_else(|| BridgeError::InvalidConfig("missing fee payer".to_string()))?
+            .pubkey();
+        let recent_blockhash = self.rpc_client.get_latest_blockhash().await?;
+        let mut tx = Transaction::new_with_payer(&all_instructions, Some(&fee_payer));
+        tx.try_sign(&unique_signers(signers), recent_blockhash)?;
+
+        self.rpc_client
+            .send_and_confirm_transaction(&tx)
+            .await
+            .map_err(|e| self.decode_client_error(e, &all_instructions))
+    }
+
+    /// Convert an RPC error raised by `instructions`, decoding errors of the client's programs
+    fn decode_client_error(&self, error: ClientError, instructions: &[Instruction]) -> BridgeError {
+        BridgeError::from_client_error(error, instructions).decode_program_error(
+            &self.bitvm_bridge_program_id,
+            &self.btc_light_client_program_id,
+        )
+    }
+
+    /// Simulate `instructions` without signing them, prepending the compute budget
+    async fn simulate_instructions(
+        &self,
+        instructions: Vec<Instruction>,
+        compute_budget: Option<ComputeBudgetConfig>,
+        default_compute_unit_limit: Option<u32>,
+        fee_payer: &Pubkey,
+    ) -> Result<SimulationResult> {
+        let all_instructions = self
+            .with_compute_budget(instructions, compute_budget, default_compute_unit_limit)
+            .await?;
+
+        let tx = Transaction::new_with_payer(&all_instructions, Some(fee_payer));
+        let result = self
+            .rpc_client
+            .simulate_transaction_with_config(
+                &tx,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    commitment: Some(self.commitment()),
+                    ..RpcSimulateTransactionConfig::default()
+                },
+            )
+            .await
+            .map_err(|e| self.decode_client_error(e, &all_instructions))?;
+
+        let mut simulation = SimulationResult::from(result.value);
+        if let Some(program_error) = &mut simulation.program_error {
+            if let Some(instruction) =
+                all_instructions.get(usize::from(program_error.instruction_index))
+            {
+                program_error.program_id = Some(instruction.program_id);
+            }
+            program_error.decode(
+                &self.bitvm_bridge_program_id,
+                &self.btc_light_client_program_id,
+            );
+        }
+        Ok(simulation)
+    }
+
+    /// Build an unsigned transaction for `instructions`, prepending the compute budget
+    async fn unsigned_transaction(
+        &self,
+        instructions: Vec<Instruction>,
+        default_compute_unit_limit: Option<u32>,
+        options: &UnsignedTransactionOptions,
+    ) -> Result<Transaction> {
+        let all_instructions = self
+            .with_compute_budget(
+                instructions,
+                options.compute_budget,
+                default_compute_unit_limit,
+            )
+            .await?;
+
+        let fee_payer = options.fee_payer.unwrap_or_else(|| self.payer());
+        let message = match options.nonce {
+            Some(nonce) => {
+                let nonce_blockhash = match options.recent_blockhash {
+                    Some(nonce_blockhash) => nonce_blockhash,
+                    None => self.get_nonce_account(&nonce.account).await?.blockhash(),
+                };
+                // The advance nonce instruction is placed first by `new_with_nonce`
+                let mut message = Message::new_with_nonce(
+                    all_instructions,
+                    Some(&fee_payer),
+                    &nonce.account,
+                    &nonce.authority,
+                );
+                message.recent_blockhash = nonce_blockhash;
+                message
+            }
+            None => {
+                let recent_blockhash = match options.recent_blockhash {
+                    Some(recent_blockhash) => recent_blockhash,
+                    None => self.rpc_client.get_latest_blockhash().await?,
+                };
+                Message::new_with_blockhash(&all_instructions, Some(&fee_payer), &recent_blockhash)
+            }
+        };
+
+        Ok(Transaction::new_unsigned(message))
+    }
+
+    /// Build an unsigned mint transaction for offline signing
+    ///
+    /// The fee payer and the mint authority must sign [`Transaction::message_data`].
+    pub async fn build_mint_transaction(
         &self,
         recipient: &str,
         tx_id: [u8; 32],
         amount: u64,
-    ) -> anyhow::Result<String> {
-        let recipient = Pubkey::from_str(recipient)?;
-        // Get bridge state PDA
-        let (bridge_state, _) =
-            Pubkey::find_program_address(&[b"bridge_state"], &self.bitvm_bridge_program.id());
+        options: UnsignedTransactionOptions,
+    ) -> Result<Transaction> {
+        let mint_authority = options.authority.unwrap_or_else(|| self.payer());
+        let instructions = self
+            .mint_instructions(
+                recipient,
+                tx_id,
+                amount,
+                &mint_authority,
+                options.preflight.unwrap_or(false),
+            )
+            .await?;
 
-        // Fetch and deserialize bridge state data
-        let bridge_state_data = self
-            .bitvm_bridge_program
-            .account::<BridgeState>(bridge_state)
+        self.unsigned_transaction(instructions, None, &options)
+            .await
+    }
+
+    /// Build an unsigned burn transaction for offline signing
+    ///
+    /// The fee payer and the token owner must sign [`Transaction::message_data`].
+    pub async fn build_burn_transaction(
+        &self,
+        amount: u64,
+        btc_addr: &str,
+        fee_rate: u32,
+        operator_id: u64,
+        options: UnsignedTransactionOptions,
+    ) -> Result<Transaction> {
+        let authority = options.authority.unwrap_or_else(|| self.payer());
+        let instructions = self
+            .burn_instructions(
+                amount,
+                btc_addr,
+                fee_rate,
+                operator_id,
+                &authority,
+                options.preflight.unwrap_or(true),
+            )
             .await?;
 
-        let mint_account = bridge_state_data.mint_account;
+        self.unsigned_transaction(instructions, None, &options)
+            .await
+    }
+
+    /// Build an unsigned transaction verification for offline signing
+    ///
+    /// Only the fee payer signs, `options.authority` is not used.
+    pub async fn build_verify_transaction(
+        &self,
+        block_height: u64,
+        tx_proof: BtcTxProof,
+        options: UnsignedTransactionOptions,
+    ) -> Result<Transaction> {
+        let fee_payer = options.fee_payer.unwrap_or_else(|| self.payer());
+        let instructions = self.verify_instructions(block_height, tx_proof, &fee_payer);
 
-        // Get associated token account for recipient
-        let ata = get_associated_token_address(&recipient, &mint_account);
+        self.unsigned_transaction(instructions, Some(VERIFY_TX_COMPUTE_UNIT_LIMIT), &options)
+            .await
+    }
 
-        // Get tx minted state PDA
-        let (tx_minted_state, _) = Pubkey::find_program_address(
-            &[b"tx_minted_state", &tx_id],
-            &self.bitvm_bridge_program.id(),
+    /// Create a durable nonce account funded by the client payer
+    ///
+    /// `nonce_account` is the new account keypair and `authority` the key allowed to advance
+    /// the nonce.
+    pub async fn create_nonce_account(
+        &self,
+        nonce_account: &(dyn Signer + Sync),
+        authority: &Pubkey,
+    ) -> Result<Signature> {
+        let lamports = self
+            .rpc_client
+            .get_minimum_balance_for_rent_exemption(NonceState::size())
+            .await?;
+        let instructions = system_instruction::create_nonce_account(
+            &self.payer(),
+            &nonce_account.pubkey(),
+            authority,
+            lamports,
         );
 
-        // Get tx verified state PDA if verification is required
-        let tx_verified_state = if bridge_state_data.skip_tx_verification {
-            None
-        } else {
-            let (tx_verified_state, _) = Pubkey::find_program_address(
-                &[b"tx_verified_state", &tx_id],
-                &self.btc_light_client_program.id(),
-            );
-            Some(tx_verified_state)
-        };
+        self.send_instructions(instructions, None, None, &[&*self.payer, nonce_account])
+            .await
+    }
 
-        let accounts = accounts::MintToken {
-            mint_authority: self.payer.pubkey(),
-            recipient,
-            mint_account,
-            associated_token_account: ata,
-            token_program: spl_token::ID,
-            associated_token_program: spl_associated_token_account::ID,
-            system_program: system_program::ID,
-            bridge_state,
-            tx_minted_state,
-            tx_verified_state,
-        };
+    /// Fetch the authority and stored nonce of a durable nonce account
+    pub async fn get_nonce_account(&self, nonce_account: &Pubkey) -> Result<NonceData> {
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(nonce_account, self.commitment())
+            .await?
+            .value
+            .ok_or(BridgeError::AccountNotFound(*nonce_account))?;
+        nonce_utils::data_from_account(&account).map_err(|e| BridgeError::Decoding(e.to_string()))
+    }
+
+    /// Send a transaction signed elsewhere and wait for its confirmation
+    pub async fn send_signed_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature> {
+        if transaction.verify_with_results().iter().any(|valid| !valid) {
+            return Err(TransactionError::SignatureFailure.into());
+        }
+
+        self.rpc_client
+            .send_and_confirm_transaction(transaction)
+            .await
+            .map_err(|e| {
+                let message = &transaction.message;
+                let program_ids: Vec<Pubkey> = message
+                    .instructions()
+                    .iter()
+                    .map(|ix| *ix.program_id(message.static_account_keys()))
+                    .collect();
+                BridgeError::from(e)
+                    .attribute_program_error(&program_ids)
+                    .decode_program_error(
+                        &self.bitvm_bridge_program_id,
+                        &self.btc_light_client_program_id,
+                    )
+            })
+    }
+
+    /// Send a bincode serialized, fully signed legacy or versioned transaction
+    pub async fn send_serialized_transaction(&self, transaction: &[u8]) -> Result<Signature> {
+        let transaction: VersionedTransaction =
+            bincode::deserialize(transaction).map_err(|e| BridgeError::Decoding(e.to_string()))?;
+        self.send_signed_transaction(&transaction).await
+    }
+
+    pub async fn mint_tokens(
+        &self,
+        recipient: &str,
+        tx_id: [u8; 32],
+        amount: u64,
+    ) -> Result<String> {
+        self.mint_tokens_with_options(recipient, tx_id, amount, TransactionOptions::default())
+            .await
+    }
+
+    /// Mint tokens with an explicit fee payer and/or mint authority
+    pub async fn mint_tokens_with_options(
+        &self,
+        recipient: &str,
+        tx_id: [u8; 32],
+        amount: u64,
+        options: TransactionOptions<'_>,
+    ) -> Result<String> {
+        let (fee_payer, mint_authority) = self.signers(&options);
+        let instructions = self
+            .mint_instructions(
+                recipient,
+                tx_id,
+                amount,
+                &mint_authority.pubkey(),
+                options.preflight.unwrap_or(false),
+            )
+            .await?;
 
         // Send mint instruction
-        let payer = self.payer.clone();
         let signature = self
-            .bitvm_bridge_program
-            .request()
-            .accounts(accounts)
-            .args(bridge_instruction::Mint { tx_id, amount })
-            .signer(payer)
-            .send()
+            .send_instructions(
+                instructions,
+                options.compute_budget,
+                None,
+                &[fee_payer, mint_authority],
+            )
             .await?;
 
         Ok(signature.to_string())
     }
 
+    /// Dry-run a mint, returning logs, consumed compute units and emitted events
+    pub async fn simulate_mint_tokens(
+        &self,
+        recipient: &str,
+        tx_id: [u8; 32],
+        amount: u64,
+        options: TransactionOptions<'_>,
+    ) -> Result<SimulationResult> {
+        let (fee_payer, mint_authority) = self.signers(&options);
+        let instructions = self
+            .mint_instructions(
+                recipient,
+                tx_id,
+                amount,
+                &mint_authority.pubkey(),
+                options.preflight.unwrap_or(false),
+            )
+            .await?;
+
+        self.simulate_instructions(
+            instructions,
+            options.compute_budget,
+            None,
+            &fee_payer.pubkey(),
+        )
+        .await
+    }
+
+    /// Check a mint against the tx minted state, the verification status and the bridge limits
+    ///
+    /// Returns [`BridgeError::MintRejected`] if the bridge would reject the mint.
+    pub async fn preflight_mint(&self, tx_id: [u8; 32], amount: u64) -> Result<()> {
+        let bridge_state = self.get_bridge_state().await?;
+        self.check_mint(&bridge_state, tx_id, amount).await
+    }
+
+    async fn check_mint(
+        &self,
+        bridge_state: &BridgeStateSnapshot,
+        tx_id: [u8; 32],
+        amount: u64,
+    ) -> Result<()> {
+        let tx_minted_state = self.get_tx_minted_state(tx_id).await?;
+        let (tx_verified_state, _) =
+            pda::tx_verified_state(&self.btc_light_client_program_id, &tx_id);
+        let tx_verified_state = self
+            .optional_account::<TxVerifiedState>(tx_verified_state)
+            .await?;
+        let verification_status = TxVerificationStatus::new(
+            bridge_state.skip_tx_verification,
+            tx_verified_state.as_ref(),
+        );
+
+        preflight::check_mint(
+            bridge_state,
+            tx_minted_state.as_ref(),
+            verification_status,
+            amount,
+        )?;
+        Ok(())
+    }
+
+    async fn mint_instructions(
+        &self,
+        recipient: &str,
+        tx_id: [u8; 32],
+        amount: u64,
+        mint_authority: &Pubkey,
+        preflight: bool,
+    ) -> Result<Vec<Instruction>> {
+        let recipient = Pubkey::from_str(recipient)?;
+        // Fetch and deserialize bridge state data
+        let bridge_state_data = self.account::<BridgeState>(self.bridge_state.0).await?;
+
+        if preflight {
+            self.check_mint(&bridge_state_data.clone().into(), tx_id, amount)
+                .await?;
+        }
+
+        Ok(vec![instructions::mint(
+            &self.bitvm_bridge_program_id,
+            &self.btc_light_client_program_id,
+            &self.bridge_state.0,
+            &bridge_state_data,
+            mint_authority,
+            &recipient,
+            tx_id,
+            amount,
+        )])
+    }
+
+    /// Burn tokens of the client payer, to be released to `btc_addr` on Bitcoin
+    ///
+    /// The burn is checked as in [`Self::preflight_burn`] before it is sent, use
+    /// [`Self::burn_tokens_with_options`] with `preflight(false)` to skip the checks.
+    ///
+    /// `operator_id` is not validated, the bridge state has no operator set to check it
+    /// against and it is only recorded in the emitted burn event.
     pub async fn burn_tokens(
         &self,
         amount: u64,
         btc_addr: &str,
         fee_rate: u32,
         operator_id: u64,
-    ) -> anyhow::Result<String> {
-        let btc_addr = btc_addr.to_string();
-        // Get bridge state PDA
-        let (bridge_state, _) =
-            Pubkey::find_program_address(&[b"bridge_state"], &self.bitvm_bridge_program.id());
+    ) -> Result<String> {
+        self.burn_tokens_with_options(
+            amount,
+            btc_addr,
+            fee_rate,
+            operator_id,
+            TransactionOptions::default(),
+        )
+        .await
+    }
 
-        // Fetch and deserialize bridge state data
-        let bridge_state_data = self
-            .bitvm_bridge_program
-            .account::<BridgeState>(bridge_state)
+    /// Burn tokens with an explicit fee payer and/or token owner
+    pub async fn burn_tokens_with_options(
+        &self,
+        amount: u64,
+        btc_addr: &str,
+        fee_rate: u32,
+        operator_id: u64,
+        options: TransactionOptions<'_>,
+    ) -> Result<String> {
+        let (fee_payer, authority) = self.signers(&options);
+        let instructions = self
+            .burn_instructions(
+                amount,
+                btc_addr,
+                fee_rate,
+                operator_id,
+                &authority.pubkey(),
+                options.preflight.unwrap_or(true),
+            )
             .await?;
 
-        let mint_account = bridge_state_data.mint_account;
-        let ata = get_associated_token_address(&self.payer.pubkey(), &mint_account);
-
-        let accounts = accounts::BurnToken {
-            authority: self.payer.pubkey(),
-            mint_account,
-            associated_token_account: ata,
-            token_program: spl_token::ID,
-            bridge_state,
-        };
-
         // Send burn instruction
-        let payer = self.payer.clone();
         let signature = self
-            .bitvm_bridge_program
-            .request()
-            .accounts(accounts)
-            .args(bridge_instruction::Burn {
+            .send_instructions(
+                instructions,
+                options.compute_budget,
+                None,
+                &[fee_payer, authority],
+            )
+            .await?;
+
+        Ok(signature.to_string())
+    }
+
+    /// Dry-run a burn, returning logs, consumed compute units and emitted events
+    pub async fn simulate_burn_tokens(
+        &self,
+        amount: u64,
+        btc_addr: &str,
+        fee_rate: u32,
+        operator_id: u64,
+        options: TransactionOptions<'_>,
+    ) -> Result<SimulationResult> {
+        let (fee_payer, authority) = self.signers(&options);
+        let instructions = self
+            .burn_instructions(
                 amount,
                 btc_addr,
                 fee_rate,
                 operator_id,
-            })
-            .signer(payer)
-            .send()
+                &authority.pubkey(),
+                options.preflight.unwrap_or(true),
+            )
             .await?;
 
-        Ok(signature.to_string())
+        self.simulate_instructions(
+            instructions,
+            options.compute_budget,
+            None,
+            &fee_payer.pubkey(),
+        )
+        .await
     }
 
-    pub async fn query_latest_block_height(&self) -> anyhow::Result<u64> {
-        let (btc_light_client_state, _) = Pubkey::find_program_address(
-            &[b"btc_light_client"],
-            &self.btc_light_client_program.id(),
-        );
+    async fn burn_instructions(
+        &self,
+        amount: u64,
+        btc_addr: &str,
+        fee_rate: u32,
+        operator_id: u64,
+        authority: &Pubkey,
+        preflight: bool,
+    ) -> Result<Vec<Instruction>> {
+        // Tokens burned to an address of another network cannot be released, check it even
+        // without preflight since it needs no RPC
+        parse_btc_address(btc_addr, self.btc_network)?;
+
+        // Fetch and deserialize bridge state data
+        let bridge_state_data = self.account::<BridgeState>(self.bridge_state.0).await?;
 
-        let btc_light_client_state_data = self
-            .btc_light_client_program
-            .account::<BtcLightClientState>(btc_light_client_state)
+        if preflight {
+            self.check_burn(
+                &bridge_state_data.clone().into(),
+                authority,
+                amount,
+                btc_addr,
+            )
             .await?;
+        }
 
-        Ok(btc_light_client_state_data.latest_block_height)
+        Ok(vec![instructions::burn(
+            &self.bitvm_bridge_program_id,
+            &self.bridge_state.0,
+            &bridge_state_data,
+            authority,
+            amount,
+            btc_addr,
+            fee_rate,
+            operator_id,
+        )])
     }
 
-    pub async fn query_min_confirmations(&self) -> anyhow::Result<u64> {
-        let (btc_light_client_state, _) = Pubkey::find_program_address(
-            &[b"btc_light_client"],
-            &self.btc_light_client_program.id(),
-        );
+    /// Check a burn against the pause flag, the token balance, the bridge limits and the
+    /// client's Bitcoin network
+    ///
+    /// Returns [`BridgeError::BurnRejected`] if the bridge would reject the burn.
+    pub async fn preflight_burn(
+        &self,
+        amount: u64,
+        btc_addr: &str,
+        authority: &Pubkey,
+    ) -> Result<()> {
+        let bridge_state = self.get_bridge_state().await?;
+        self.check_burn(&bridge_state, authority, amount, btc_addr)
+            .await
+    }
+
+    async fn check_burn(
+        &self,
+        bridge_state: &BridgeStateSnapshot,
+        authority: &Pubkey,
+        amount: u64,
+        btc_addr: &str,
+    ) -> Result<()> {
+        let token_account = get_associated_token_address(authority, &bridge_state.mint_account);
+        let token_balance = self
+            .optional_account::<TokenAccount>(token_account)
+            .await?
+            .map(|account| account.amount);
+
+        preflight::check_burn(
+            bridge_state,
+            &token_account,
+            token_balance,
+            amount,
+            btc_addr,
+            self.btc_network,
+        )?;
+        Ok(())
+    }
+
+    /// Fetch the bridge configuration: mint, owner, limits and pause flags
+    pub async fn get_bridge_state(&self) -> Result<BridgeStateSnapshot> {
+        let bridge_state = self.account::<BridgeState>(self.bridge_state.0).await?;
+        Ok(bridge_state.into())
+    }
 
-        let btc_light_client_state_data = self
-            .btc_light_client_program
-            .account::<BtcLightClientState>(btc_light_client_state)
+    /// Fetch the light client tip and confirmation policy in a single RPC call
+    pub async fn get_light_client_state(&self) -> Result<BtcLightClientStateSnapshot> {
+        let state = self
+            .account::<BtcLightClientState>(self.btc_light_client_state.0)
             .await?;
+        Ok(state.into())
+    }
+
+    /// Fetch the block hash stored by the light client at `block_height`, `None` if the light
+    /// client does not know the block
+    pub async fn get_block_hash_entry(
+        &self,
+        block_height: u64,
+    ) -> Result<Option<BlockHashEntrySnapshot>> {
+        let (block_hash_entry, _) =
+            pda::block_hash_entry(&self.btc_light_client_program_id, block_height);
+        let entry = self
+            .optional_account::<BlockHashEntry>(block_hash_entry)
+            .await?;
+        Ok(entry.map(Into::into))
+    }
+
+    /// Parse a block header and check it against the light client before verifying a proof
+    ///
+    /// The header must satisfy its proof-of-work target and hash to the block hash stored in
+    /// the light client's block hash entry at `block_height`.
+    pub async fn validate_block_header(
+        &self,
+        block_height: u64,
+        block_header: &[u8],
+    ) -> Result<BlockHeader> {
+        let header = BlockHeader::parse(block_header)?;
+        if !header.check_pow() {
+            return Err(BridgeError::InvalidBlockHeader(format!(
+                "block {} does not meet its proof-of-work target",
+                header.block_hash_hex()
+            )));
+        }
+
+        let entry = self
+            .get_block_hash_entry(block_height)
+            .await?
+            .ok_or_else(|| {
+                BridgeError::InvalidBlockHeader(format!(
+                    "light client has no block at height {}",
+                    block_height
+                ))
+            })?;
+        if entry.hash != header.block_hash() {
+            return Err(BridgeError::InvalidBlockHeader(format!(
+                "block {} does not match {} stored by the light client at height {}",
+                header.block_hash_hex(),
+                entry.hash_hex(),
+                block_height
+            )));
+        }
+        Ok(header)
+    }
+
+    /// Fetch the block hashes stored for a range of heights with `getMultipleAccounts`
+    ///
+    /// Returns one `(height, entry)` pair per height in the range, in order. The range may
+    /// span at most [`MAX_BLOCK_HASH_ENTRIES`] heights.
+    pub async fn get_block_hash_entries(
+        &self,
+        block_heights: RangeInclusive<u64>,
+    ) -> Result<Vec<(u64, Option<BlockHashEntrySnapshot>)>> {
+        let heights = block_height_range(block_heights)?;
+        let addresses: Vec<Pubkey> = heights
+            .iter()
+            .map(|height| pda::block_hash_entry(&self.btc_light_client_program_id, *height).0)
+            .collect();
+        let entries = self.multiple_accounts::<BlockHashEntry>(&addresses).await?;
 
-        Ok(btc_light_client_state_data.min_confirmations)
+        Ok(heights
+            .into_iter()
+            .zip(entries)
+            .map(|(height, entry)| (height, entry.map(Into::into)))
+            .collect())
+    }
+
+    /// Whether the light client has `min_confirmations` on top of the block at `block_height`
+    pub async fn is_block_sufficiently_confirmed(&self, block_height: u64) -> Result<bool> {
+        Ok(self
+            .get_light_client_state()
+            .await?
+            .is_sufficiently_confirmed(block_height))
+    }
+
+    /// Poll the light client until the block at `block_height` is sufficiently confirmed
+    ///
+    /// Returns the light client state that satisfied the check, or
+    /// [`BridgeError::ConfirmationTimeout`] once `timeout` elapses.
+    pub async fn wait_for_confirmations(
+        &self,
+        block_height: u64,
+        timeout: Duration,
+    ) -> Result<BtcLightClientStateSnapshot> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let state = self.get_light_client_state().await?;
+            if state.is_sufficiently_confirmed(block_height) {
+                return Ok(state);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(BridgeError::ConfirmationTimeout {
+                    block_height,
+                    confirmations: state.confirmations(block_height),
+                    min_confirmations: state.min_confirmations,
+                });
+            }
+            tokio::time::sleep(CONFIRMATION_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+
+    pub async fn query_latest_block_height(&self) -> Result<u64> {
+        Ok(self.get_light_client_state().await?.latest_block_height)
+    }
+
+    pub async fn query_min_confirmations(&self) -> Result<u64> {
+        Ok(self.get_light_client_state().await?.min_confirmations)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -209,21 +1318,43 @@ impl BitvmBridgeClient {
         output_index: u32,
         expected_amount: u64,
         expected_script_hash: [u8; 32],
-    ) -> anyhow::Result<String> {
-        // get block_hash_entry PDA
-        let (block_hash_entry, _) = Pubkey::find_program_address(
-            &[b"block_hash_entry", &block_height.to_le_bytes()],
-            &self.btc_light_client_program.id(),
-        );
+    ) -> Result<String> {
+        self.verify_transaction_with_options(
+            block_height,
+            block_header,
+            tx_id,
+            tx_index,
+            merkle_proof,
+            raw_tx,
+            output_index,
+            expected_amount,
+            expected_script_hash,
+            TransactionOptions::default(),
+        )
+        .await
+    }
 
-        // get tx_verified_state PDA
-        let (tx_verified_state, _) = Pubkey::find_program_address(
-            &[b"tx_verified_state", &tx_id],
-            &self.btc_light_client_program.id(),
-        );
+    /// Verify a Bitcoin transaction with an explicit fee payer and/or compute budget
+    ///
+    /// `options.authority` is not used, the light client only needs a fee payer.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn verify_transaction_with_options(
+        &self,
+        block_height: u64,
+        block_header: &[u8],
+        tx_id: [u8; 32],
+        tx_index: u32,
+        merkle_proof: Vec<[u8; 32]>,
+        raw_tx: &[u8],
+        output_index: u32,
+        expected_amount: u64,
+        expected_script_hash: [u8; 32],
+        options: TransactionOptions<'_>,
+    ) -> Result<String> {
+        let (fee_payer, _) = self.signers(&options);
 
         // build tx proof
-        let tx_proof = btc_light_client::instructions::verify_tx::BtcTxProof {
+        let tx_proof = BtcTxProof {
             block_header: block_header.to_vec(),
             tx_id,
             tx_index,
@@ -233,74 +1364,179 @@ impl BitvmBridgeClient {
             expected_amount,
             expected_script_hash,
         };
+        let instructions = self.verify_instructions(block_height, tx_proof, &fee_payer.pubkey());
 
-        let (btc_light_client_state, _) = Pubkey::find_program_address(
-            &[b"btc_light_client"],
-            &self.btc_light_client_program.id(),
-        );
+        // send verify transaction instruction
+        let signature = self
+            .send_instructions(
+                instructions,
+                options.compute_budget,
+                Some(VERIFY_TX_COMPUTE_UNIT_LIMIT),
+                &[fee_payer],
+            )
+            .await?;
+
+        Ok(signature.to_string())
+    }
 
-        // build accounts
-        let accounts = btc_light_client::accounts::VerifyTransaction {
-            state: btc_light_client_state,
-            tx_verified_state,
-            payer: self.payer.pubkey(),
-            system_program: system_program::ID,
-            block_hash_entry,
+    /// Dry-run a transaction verification, returning logs and consumed compute units
+    #[allow(clippy::too_many_arguments)]
+    pub async fn simulate_verify_transaction(
+        &self,
+        block_height: u64,
+        block_header: &[u8],
+        tx_id: [u8; 32],
+        tx_index: u32,
+        merkle_proof: Vec<[u8; 32]>,
+        raw_tx: &[u8],
+        output_index: u32,
+        expected_amount: u64,
+        expected_script_hash: [u8; 32],
+        options: TransactionOptions<'_>,
+    ) -> Result<SimulationResult> {
+        let (fee_payer, _) = self.signers(&options);
+
+        let tx_proof = BtcTxProof {
+            block_header: block_header.to_vec(),
+            tx_id,
+            tx_index,
+            merkle_proof,
+            raw_tx: raw_tx.to_vec(),
+            output_index,
+            expected_amount,
+            expected_script_hash,
         };
+        let instructions = self.verify_instructions(block_height, tx_proof, &fee_payer.pubkey());
 
-        let request_compute_units_instruction =
-            ComputeBudgetInstruction::set_compute_unit_limit(500000);
+        self.simulate_instructions(
+            instructions,
+            options.compute_budget,
+            Some(VERIFY_TX_COMPUTE_UNIT_LIMIT),
+            &fee_payer.pubkey(),
+        )
+        .await
+    }
 
-        // send verify transaction instruction
-        let payer = self.payer.clone();
-        let signature = self
-            .btc_light_client_program
-            .request()
-            .instruction(request_compute_units_instruction)
-            .accounts(accounts)
-            .args(btc_light_client::instruction::VerifyTransaction {
-                block_height,
-                tx_proof,
-            })
-            .signer(payer)
-            .send()
+    fn verify_instructions(
+        &self,
+        block_height: u64,
+        tx_proof: BtcTxProof,
+        payer: &Pubkey,
+    ) -> Vec<Instruction> {
+        vec![instructions::verify_transaction(
+            &self.btc_light_client_program_id,
+            &self.btc_light_client_state.0,
+            payer,
+            block_height,
+            tx_proof,
+        )]
+    }
+
+    /// Fetch the mint record of a Bitcoin transaction, `None` if it was never minted
+    pub async fn get_tx_minted_state(
+        &self,
+        tx_id: [u8; 32],
+    ) -> Result<Option<TxMintedStateSnapshot>> {
+        let (tx_minted_state, _) = pda::tx_minted_state(&self.bitvm_bridge_program_id, &tx_id);
+        let state = self
+            .optional_account::<TxMintedState>(tx_minted_state)
             .await?;
+        Ok(state.map(|state| TxMintedStateSnapshot::new(tx_id, state)))
+    }
 
-        Ok(signature.to_string())
+    /// Fetch the mint records of many Bitcoin transactions with `getMultipleAccounts`
+    ///
+    /// The result is in the order of `tx_ids`.
+    pub async fn get_tx_minted_states(
+        &self,
+        tx_ids: &[[u8; 32]],
+    ) -> Result<Vec<Option<TxMintedStateSnapshot>>> {
+        let addresses: Vec<Pubkey> = tx_ids
+            .iter()
+            .map(|tx_id| pda::tx_minted_state(&self.bitvm_bridge_program_id, tx_id).0)
+            .collect();
+        let states = self.multiple_accounts::<TxMintedState>(&addresses).await?;
+
+        Ok(tx_ids
+            .iter()
+            .zip(states)
+            .map(|(tx_id, state)| state.map(|state| TxMintedStateSnapshot::new(*tx_id, state)))
+            .collect())
+    }
+
+    /// Whether tokens were already minted for a Bitcoin transaction
+    pub async fn is_tx_minted(&self, tx_id: [u8; 32]) -> Result<bool> {
+        Ok(self
+            .get_tx_minted_state(tx_id)
+            .await?
+            .is_some_and(|state| state.is_minted))
+    }
+
+    /// Batch version of [`Self::is_tx_minted`], in the order of `tx_ids`
+    pub async fn are_txs_minted(&self, tx_ids: &[[u8; 32]]) -> Result<Vec<bool>> {
+        Ok(self
+            .get_tx_minted_states(tx_ids)
+            .await?
+            .into_iter()
+            .map(|state| state.is_some_and(|state| state.is_minted))
+            .collect())
     }
-    pub async fn get_tx_verification_status(&self, tx_id: [u8; 32]) -> anyhow::Result<bool> {
-        // Get bridge state PDA
-        let (bridge_state, _) =
-            Pubkey::find_program_address(&[b"bridge_state"], &self.bitvm_bridge_program.id());
 
+    /// Verification status of a Bitcoin transaction, taking the bridge skip flag into account
+    pub async fn get_tx_verification_status(
+        &self,
+        tx_id: [u8; 32],
+    ) -> Result<TxVerificationStatus> {
         // Fetch and deserialize bridge state data
-        let bridge_state_data = self
-            .bitvm_bridge_program
-            .account::<BridgeState>(bridge_state)
+        let bridge_state_data = self.account::<BridgeState>(self.bridge_state.0).await?;
+
+        let (tx_verified_state, _) =
+            pda::tx_verified_state(&self.btc_light_client_program_id, &tx_id);
+        let tx_verified_state_data = self
+            .optional_account::<TxVerifiedState>(tx_verified_state)
             .await?;
-        if bridge_state_data.skip_tx_verification {
-            return Ok(true);
-        }
 
-        let (tx_verified_state, _) = Pubkey::find_program_address(
-            &[b"tx_verified_state", &tx_id],
-            &self.btc_light_client_program.id(),
-        );
+        Ok(TxVerificationStatus::new(
+            bridge_state_data.skip_tx_verification,
+            tx_verified_state_data.as_ref(),
+        ))
+    }
 
-        let tx_verified_state_data = self
-            .btc_light_client_program
-            .account::<btc_light_client::state::TxVerifiedState>(tx_verified_state)
+    /// Batch version of [`Self::get_tx_verification_status`] using `getMultipleAccounts`,
+    /// in the order of `tx_ids`
+    pub async fn get_tx_verification_statuses(
+        &self,
+        tx_ids: &[[u8; 32]],
+    ) -> Result<Vec<TxVerificationStatus>> {
+        let bridge_state_data = self.account::<BridgeState>(self.bridge_state.0).await?;
+
+        let addresses: Vec<Pubkey> = tx_ids
+            .iter()
+            .map(|tx_id| pda::tx_verified_state(&self.btc_light_client_program_id, tx_id).0)
+            .collect();
+        let states = self
+            .multiple_accounts::<TxVerifiedState>(&addresses)
             .await?;
 
-        Ok(tx_verified_state_data.is_verified)
+        Ok(states
+            .iter()
+            .map(|state| {
+                TxVerificationStatus::new(bridge_state_data.skip_tx_verification, state.as_ref())
+            })
+            .collect())
     }
-    pub fn validate_solana_address(address: &str) -> anyhow::Result<()> {
-        Pubkey::from_str(address)?;
-        Ok(())
+
+    pub fn is_valid_solana_address(&self, address: &str) -> bool {
+        BitvmBridgeClient::validate_solana_address(address).is_ok()
+    }
+
+    /// Check a burn address against the client's Bitcoin network
+    pub fn is_valid_btc_address(&self, address: &str) -> bool {
+        parse_btc_address(address, self.btc_network).is_ok()
     }
 }
 
-impl std::ops::Deref for BitvmBridgeClient {
+impl<S> std::ops::Deref for BitvmBridgeClient<S> {
     type Target = QueryClient;
 
     fn deref(&self) -> &Self::Target {
@@ -310,45 +1546,288 @@ impl std::ops::Deref for BitvmBridgeClient {
 
 #[cfg(test)]
 mod tests {
-    use super::BitvmBridgeClient;
-    use crate::query_client::QueryClient;
+    use super::{
+        block_height_range, unique_signers, BitvmBridgeClient, BitvmBridgeClientBuilder,
+        TransactionOptions, UnsignedTransactionOptions, MAX_BLOCK_HASH_ENTRIES,
+    };
+    use crate::{btc::Network, compute_budget::PriorityFee, error::BridgeError, pda};
     use anchor_client::{
-        solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Keypair},
-        Client, Cluster,
+        solana_sdk::{
+            commitment_config::CommitmentConfig,
+            hash::Hash,
+            pubkey::Pubkey,
+            signature::{write_keypair_file, Keypair, Signer},
+            system_program,
+            transaction::VersionedTransaction,
+        },
+        Cluster,
     };
-    use std::{str::FromStr, sync::Arc};
+    use btc_light_client::instructions::verify_tx::BtcTxProof;
+    use std::{ops::RangeInclusive, str::FromStr, time::Duration};
 
     #[test]
-    fn test_is_valid_solana_address() {
-        // This is just a mock implementation for unit testing
-        // Mock the client dependencies to allow offline testing
-        let private_key = Keypair::new();
-        let payer = Arc::new(private_key);
-        let cluster = Cluster::Custom(
-            "http://localhost:8899".to_string(),
-            "http://localhost:8899".to_string(),
+    fn test_builder_resolves_endpoints() {
+        let client = BitvmBridgeClient::builder()
+            .cluster(Cluster::Devnet)
+            .payer(Keypair::new())
+            .build()
+            .unwrap();
+        assert_eq!(client.cluster(), &Cluster::Devnet);
+        assert_eq!(client.commitment(), CommitmentConfig::confirmed());
+        assert_eq!(client.bitvm_bridge_program_id(), bitvm_bridge::ID);
+        assert_eq!(client.btc_light_client_program_id(), btc_light_client::ID);
+        assert_eq!(
+            client.bridge_state_pda(),
+            pda::bridge_state(&bitvm_bridge::ID)
+        );
+        assert_eq!(
+            client.btc_light_client_state_pda(),
+            pda::btc_light_client_state(&btc_light_client::ID)
         );
 
-        let client =
-            Client::new_with_options(cluster, payer.clone(), CommitmentConfig::confirmed());
-        let dummy_program_id = "11111111111111111111111111111111"; // System Program ID
+        let client = BitvmBridgeClient::builder()
+            .url("http://localhost:8899")
+            .commitment(CommitmentConfig::finalized())
+            .timeout(Duration::from_secs(5))
+            .compute_unit_price(1_000)
+            .payer(Keypair::new())
+            .build()
+            .unwrap();
+        assert_eq!(client.cluster().url(), "http://localhost:8899");
+        assert_eq!(client.cluster().ws_url(), "ws://localhost:8900/");
+        assert_eq!(client.commitment(), CommitmentConfig::finalized());
+        assert_eq!(
+            client.compute_budget().priority_fee,
+            PriorityFee::Fixed(1_000)
+        );
 
-        let bitvm_bridge_program = client
-            .program(Pubkey::from_str(dummy_program_id).unwrap())
+        let client = BitvmBridgeClient::builder()
+            .url("https://rpc.example.com")
+            .ws_url("wss://ws.example.com")
+            .payer(Keypair::new())
+            .build()
             .unwrap();
-        let btc_light_client_program = client
-            .program(Pubkey::from_str(dummy_program_id).unwrap())
+        assert_eq!(
+            client.cluster(),
+            &Cluster::Custom(
+                "https://rpc.example.com".to_string(),
+                "wss://ws.example.com".to_string()
+            )
+        );
+
+        assert!(BitvmBridgeClientBuilder::<Keypair>::new().build().is_err());
+    }
+
+    #[test]
+    fn test_pluggable_signers() {
+        let keypair = Keypair::new();
+        let pubkey = keypair.pubkey();
+        let path = std::env::temp_dir().join(format!("bitvm-bridge-sdk-{}.json", pubkey));
+        write_keypair_file(&keypair, &path).unwrap();
+
+        let client = BitvmBridgeClient::new_with_keypair_file(
+            "http://localhost:8899",
+            "11111111111111111111111111111111",
+            "11111111111111111111111111111111",
+            &path,
+        )
+        .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(client.payer(), pubkey);
+
+        let signer: Box<dyn Signer + Send + Sync> = Box::new(keypair);
+        let client = BitvmBridgeClient::builder()
+            .url("http://localhost:8899")
+            .payer(signer)
+            .build()
             .unwrap();
+        assert_eq!(client.payer(), pubkey);
 
-        let query_client = QueryClient::new("http://localhost:8899".to_string()).unwrap();
+        let client = BitvmBridgeClientBuilder::new()
+            .private_key(&bs58::encode(Keypair::new().to_bytes()).into_string())
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_ne!(client.payer(), pubkey);
+    }
 
-        let client = BitvmBridgeClient {
-            query_client,
-            payer,
-            bitvm_bridge_program,
-            btc_light_client_program,
+    #[test]
+    fn test_block_height_range() {
+        assert_eq!(
+            block_height_range(100..=103).unwrap(),
+            vec![100, 101, 102, 103]
+        );
+        assert!(block_height_range(RangeInclusive::new(103, 100))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            block_height_range(0..=MAX_BLOCK_HASH_ENTRIES - 1)
+                .unwrap()
+                .len(),
+            MAX_BLOCK_HASH_ENTRIES as usize
+        );
+        assert!(block_height_range(0..=MAX_BLOCK_HASH_ENTRIES).is_err());
+        assert!(block_height_range(0..=u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_transaction_signers() {
+        let payer = Keypair::new();
+        let payer_pubkey = payer.pubkey();
+        let client = BitvmBridgeClient::builder()
+            .url("http://localhost:8899")
+            .payer(payer)
+            .build()
+            .unwrap();
+
+        let (fee_payer, authority) = client.signers(&TransactionOptions::new());
+        assert_eq!(fee_payer.pubkey(), payer_pubkey);
+        assert_eq!(authority.pubkey(), payer_pubkey);
+        assert_eq!(unique_signers(&[fee_payer, authority]).len(), 1);
+
+        let other_fee_payer = Keypair::new();
+        let other_authority = Keypair::new();
+        let options = TransactionOptions::new()
+            .fee_payer(&other_fee_payer)
+            .authority(&other_authority);
+        let (fee_payer, authority) = client.signers(&options);
+        assert_eq!(fee_payer.pubkey(), other_fee_payer.pubkey());
+        assert_eq!(authority.pubkey(), other_authority.pubkey());
+        let signers = unique_signers(&[fee_payer, authority]);
+        assert_eq!(signers.len(), 2);
+        assert_eq!(signers[0].pubkey(), other_fee_payer.pubkey());
+
+        // Authority only, the client payer still pays the fees
+        let options = TransactionOptions::new().authority(&other_authority);
+        let (fee_payer, authority) = client.signers(&options);
+        assert_eq!(fee_payer.pubkey(), payer_pubkey);
+        assert_eq!(authority.pubkey(), other_authority.pubkey());
+    }
+
+    #[tokio::test]
+    async fn test_build_unsigned_transaction() {
+        let client = BitvmBridgeClient::builder()
+            .url("http://localhost:8899")
+            .payer(Keypair::new())
+            .build()
+            .unwrap();
+        let fee_payer = Keypair::new();
+        let recent_blockhash = Hash::new_unique();
+        let tx_proof = || BtcTxProof {
+            block_header: vec![0; 80],
+            tx_id: [1; 32],
+            tx_index: 0,
+            merkle_proof: vec![],
+            raw_tx: vec![],
+            output_index: 0,
+            expected_amount: 0,
+            expected_script_hash: [0; 32],
         };
 
+        let mut tx = client
+            .build_verify_transaction(
+                100,
+                tx_proof(),
+                UnsignedTransactionOptions::new()
+                    .fee_payer(fee_payer.pubkey())
+                    .recent_blockhash(recent_blockhash),
+            )
+            .await
+            .unwrap();
+        assert_eq!(tx.message.account_keys[0], fee_payer.pubkey());
+        assert_eq!(tx.message.recent_blockhash, recent_blockhash);
+        assert_eq!(tx.message.instructions.len(), 2);
+        assert!(!tx.is_signed());
+
+        let unsigned = VersionedTransaction::from(tx.clone());
+        assert!(client.send_signed_transaction(&unsigned).await.is_err());
+
+        tx.try_sign(&[&fee_payer], recent_blockhash).unwrap();
+        let serialized = bincode::serialize(&tx).unwrap();
+        let deserialized: VersionedTransaction = bincode::deserialize(&serialized).unwrap();
+        assert!(deserialized
+            .verify_with_results()
+            .iter()
+            .all(|valid| *valid));
+
+        let nonce_account = Pubkey::new_unique();
+        let tx = client
+            .build_verify_transaction(
+                100,
+                tx_proof(),
+                UnsignedTransactionOptions::new()
+                    .fee_payer(fee_payer.pubkey())
+                    .recent_blockhash(recent_blockhash)
+                    .nonce(nonce_account, fee_payer.pubkey()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(tx.message.recent_blockhash, recent_blockhash);
+        assert_eq!(tx.message.instructions.len(), 3);
+        let advance_nonce = &tx.message.instructions[0];
+        assert_eq!(
+            tx.message.account_keys[usize::from(advance_nonce.program_id_index)],
+            system_program::ID
+        );
+        assert_eq!(
+            tx.message.account_keys[usize::from(advance_nonce.accounts[0])],
+            nonce_account
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burn_address_network() {
+        let client = BitvmBridgeClient::builder()
+            .url("http://localhost:8899")
+            .btc_network(Network::Regtest)
+            .payer(Keypair::new())
+            .build()
+            .unwrap();
+
+        // Rejected before the bridge state is fetched, even with preflight disabled
+        let error = client
+            .build_burn_transaction(
+                1_000,
+                "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+                1,
+                0,
+                UnsignedTransactionOptions::new().preflight(false),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, BridgeError::InvalidBtcAddress(_)));
+    }
+
+    #[test]
+    fn test_is_valid_btc_address() {
+        let address = "bcrt1phcnl4zcl2fu047pv4wx6y058v8u0n02at6lthvm7pcf2wrvjm5tqatn90k";
+        let client = BitvmBridgeClient::builder()
+            .url("http://localhost:8899")
+            .btc_network(Network::Regtest)
+            .payer(Keypair::new())
+            .build()
+            .unwrap();
+        assert!(client.is_valid_btc_address(address));
+        assert!(!client.is_valid_btc_address("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"));
+
+        assert!(BitvmBridgeClient::validate_btc_address(address, Network::Regtest).is_ok());
+        assert!(BitvmBridgeClient::validate_btc_address(address, Network::Bitcoin).is_err());
+    }
+
+    #[test]
+    fn test_is_valid_solana_address() {
+        // This is just a mock implementation for unit testing
+        // Mock the client dependencies to allow offline testing
+        let dummy_program_id = "11111111111111111111111111111111"; // System Program ID
+
+        let client = BitvmBridgeClient::builder()
+            .url("http://localhost:8899")
+            .bitvm_bridge_program_id(Pubkey::from_str(dummy_program_id).unwrap())
+            .btc_light_client_program_id(Pubkey::from_str(dummy_program_id).unwrap())
+            .payer(Keypair::new())
+            .build()
+            .unwrap();
+
         // Valid Solana addresses (base58 encoded)
         assert!(client.is_valid_solana_address("11111111111111111111111111111111")); // System Program
         assert!(client.is_valid_solana_address("AgCVMw9PkZqBCGiupLhdgKAcudKcjyUppShJCsJa7fY3")); // Random valid address
@@ -358,6 +1837,7 @@ mod tests {
         assert!(!client.is_valid_solana_address("0x0000000000000000000000000000000000000000")); // Ethereum style
         assert!(!client.is_valid_solana_address("not-a-valid-address"));
         assert!(!client.is_valid_solana_address("11111111")); // Too short
+        assert!(BitvmBridgeClient::validate_solana_address("11111111").is_err());
         assert!(!client
             .is_valid_solana_address("111111111111111111111111111111111111111111111111111111"));
         // Too long
@@ -0,0 +1,86 @@
+//! Bitcoin address validation for burns
+
+use bitcoin::{
+    address::{Address, AddressType, NetworkUnchecked},
+    Network,
+};
+use thiserror::Error;
+
+/// Reason a Bitcoin address is not accepted by the bridge
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BtcAddressError {
+    #[error("{0}")]
+    Parse(String),
+
+    #[error("{0}")]
+    WrongNetwork(String),
+
+    #[error("address type is not P2PKH, P2SH, P2WPKH, P2WSH or P2TR")]
+    UnsupportedType,
+}
+
+/// Parse a Bitcoin address, checking it against `network` when given
+///
+/// Only P2PKH, P2SH, P2WPKH, P2WSH and P2TR addresses are accepted.
+pub fn parse_btc_address(
+    address: &str,
+    network: Option<Network>,
+) -> Result<Address, BtcAddressError> {
+    let address = address
+        .parse::<Address<NetworkUnchecked>>()
+        .map_err(|e| BtcAddressError::Parse(e.to_string()))?;
+    let address = match network {
+        Some(network) => address
+            .require_network(network)
+            .map_err(|e| BtcAddressError::WrongNetwork(e.to_string()))?,
+        None => address.assume_checked(),
+    };
+
+    match address.address_type() {
+        Some(
+            AddressType::P2pkh
+            | AddressType::P2sh
+            | AddressType::P2wpkh
+            | AddressType::P2wsh
+            | AddressType::P2tr,
+        ) => Ok(address),
+        _ => Err(BtcAddressError::UnsupportedType),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_btc_address() {
+        let mainnet = [
+            "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+            "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+            "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3",
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+        ];
+        for address in mainnet {
+            assert!(parse_btc_address(address, Some(Network::Bitcoin)).is_ok());
+            assert!(matches!(
+                parse_btc_address(address, Some(Network::Testnet)),
+                Err(BtcAddressError::WrongNetwork(_))
+            ));
+        }
+
+        let regtest = "bcrt1phcnl4zcl2fu047pv4wx6y058v8u0n02at6lthvm7pcf2wrvjm5tqatn90k";
+        assert!(parse_btc_address(regtest, Some(Network::Regtest)).is_ok());
+        assert!(parse_btc_address(regtest, None).is_ok());
+        assert!(parse_btc_address(regtest, Some(Network::Signet)).is_err());
+
+        assert!(matches!(
+            parse_btc_address("bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs", None),
+            Err(BtcAddressError::UnsupportedType)
+        ));
+        assert!(matches!(
+            parse_btc_address("not-an-address", None),
+            Err(BtcAddressError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+//! Bitcoin block headers as passed to `verify_transaction`
+
+use bitcoin::{
+    block::{Header, Version},
+    consensus,
+    hashes::Hash,
+    BlockHash, CompactTarget, TxMerkleNode,
+};
+
+use crate::{
+    btc::hash_to_hex,
+    error::{BridgeError, Result},
+};
+
+/// Size of a serialized block header
+pub const BLOCK_HEADER_SIZE: usize = 80;
+
+/// Bitcoin block header
+///
+/// Hashes are in internal byte order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub version: i32,
+    pub prev_block_hash: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub time: u32,
+    pub bits: u32,
+    pub nonce: u32,
+}
+
+impl BlockHeader {
+    /// Parse an 80-byte serialized header
+    pub fn parse(block_header: &[u8]) -> Result<Self> {
+        if block_header.len() != BLOCK_HEADER_SIZE {
+            return Err(BridgeError::InvalidBlockHeader(format!(
+                "expected {} bytes, got {}",
+                BLOCK_HEADER_SIZE,
+                block_header.len()
+            )));
+        }
+        let header: Header = consensus::deserialize(block_header)
+            .map_err(|e| BridgeError::InvalidBlockHeader(e.to_string()))?;
+        Ok(header.into())
+    }
+
+    /// Parse a hex encoded header, as returned by `getblockheader <hash> false`
+    pub fn from_hex(block_header: &str) -> Result<Self> {
+        let block_header = hex::decode(block_header)
+            .map_err(|e| BridgeError::InvalidBlockHeader(e.to_string()))?;
+        Self::parse(&block_header)
+    }
+
+    pub fn serialize(&self) -> [u8; BLOCK_HEADER_SIZE] {
+        let mut block_header = [0u8; BLOCK_HEADER_SIZE];
+        block_header.copy_from_slice(&consensus::serialize(&Header::from(*self)));
+        block_header
+    }
+
+    /// Block hash in internal byte order
+    pub fn block_hash(&self) -> [u8; 32] {
+        Header::from(*self).block_hash().to_byte_array()
+    }
+
+    /// Hex of [`Self::block_hash`], see [`hash_to_hex`]
+    pub fn block_hash_hex(&self) -> String {
+        hash_to_hex(&self.block_hash())
+    }
+
+    /// Whether the block hash meets the target encoded in `bits`
+    ///
+    /// This does not check that `bits` is the difficulty required by the chain at this height,
+    /// the light client tracks that.
+    pub fn check_pow(&self) -> bool {
+        let header = Header::from(*self);
+        header.validate_pow(header.target()).is_ok()
+    }
+}
+
+impl From<Header> for BlockHeader {
+    fn from(header: Header) -> Self {
+        Self {
+            version: header.version.to_consensus(),
+            prev_block_hash: header.prev_blockhash.to_byte_array(),
+            merkle_root: header.merkle_root.to_byte_array(),
+            time: header.time,
+            bits: header.bits.to_consensus(),
+            nonce: header.nonce,
+        }
+    }
+}
+
+impl From<BlockHeader> for Header {
+    fn from(header: BlockHeader) -> Self {
+        Self {
+            version: Version::from_consensus(header.version),
+            prev_blockhash: BlockHash::from_byte_array(header.prev_block_hash),
+            merkle_root: TxMerkleNode::from_byte_array(header.merkle_root),
+            time: header.time,
+            bits: CompactTarget::from_consensus(header.bits),
+            nonce: header.nonce,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::BLOCK_HEADER;
+
+    #[test]
+    fn test_block_header_roundtrip() {
+        let header = BlockHeader::from_hex(BLOCK_HEADER).unwrap();
+        assert_eq!(header.version, 0x2000_0000);
+        assert_eq!(header.bits, 0x1d00_ffff);
+        assert_eq!(hex::encode(header.serialize()), BLOCK_HEADER);
+        assert!(header.check_pow());
+        assert!(header.block_hash_hex().starts_with("00000000"));
+        assert_eq!(
+            header.block_hash_hex(),
+            Header::from(header).block_hash().to_string()
+        );
+
+        let tampered = BlockHeader {
+            nonce: header.nonce.wrapping_add(1),
+            ..header
+        };
+        assert!(!tampered.check_pow());
+
+        assert!(BlockHeader::parse(&[0; 79]).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+//! Merkle branches of Bitcoin transactions for `BtcTxProof`
+//!
+//! Hashes are in internal byte order, the order used by `BtcTxProof::tx_id` and
+//! `BtcTxProof::merkle_proof`.
+
+use bitcoin::{
+    block::Block,
+    consensus,
+    hashes::{sha256d, Hash},
+};
+
+use crate::{
+    btc::{hash_to_hex, header::BlockHeader},
+    error::{BridgeError, Result},
+};
+
+/// Merkle branch proving that a transaction is included in a block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleBranch {
+    /// Position of the transaction in the block
+    pub tx_index: u32,
+    /// Sibling hashes from the leaf up to the root
+    pub hashes: Vec<[u8; 32]>,
+}
+
+impl MerkleBranch {
+    /// Compute the branch of `tx_id` in a block with transactions `tx_ids`, in block order
+    pub fn from_tx_ids(tx_ids: &[[u8; 32]], tx_id: &[u8; 32]) -> Result<Self> {
+        let tx_index = tx_ids.iter().position(|id| id == tx_id).ok_or_else(|| {
+            BridgeError::InvalidBtcTransaction(format!(
+                "transaction {} is not in the block",
+                hash_to_hex(tx_id)
+            ))
+        })?;
+
+        let mut hashes = Vec::new();
+        let mut level = tx_ids.to_vec();
+        let mut index = tx_index;
+        while level.len() > 1 {
+            // An odd level is completed by duplicating its last hash
+            if level.len() % 2 == 1 {
+                level.push(level[level.len() - 1]);
+            }
+            hashes.push(level[index ^ 1]);
+            level = level
+                .chunks(2)
+                .map(|pair| hash_pair(&pair[0], &pair[1]))
+                .collect();
+            index /= 2;
+        }
+
+        Ok(Self {
+            tx_index: tx_index as u32,
+            hashes,
+        })
+    }
+
+    /// Compute the branch of `tx_id` in a consensus serialized block
+    pub fn from_raw_block(raw_block: &[u8], tx_id: &[u8; 32]) -> Result<Self> {
+        let block: Block = consensus::deserialize(raw_block)
+            .map_err(|e| BridgeError::Decoding(format!("invalid bitcoin block: {}", e)))?;
+        let tx_ids: Vec<[u8; 32]> = block
+            .txdata
+            .iter()
+            .map(|tx| tx.txid().to_byte_array())
+            .collect();
+        Self::from_tx_ids(&tx_ids, tx_id)
+    }
+
+    /// Merkle root obtained by hashing `tx_id` up the branch
+    pub fn compute_root(&self, tx_id: &[u8; 32]) -> [u8; 32] {
+        let mut hash = *tx_id;
+        let mut index = self.tx_index;
+        for sibling in &self.hashes {
+            hash = if index & 1 == 0 {
+                hash_pair(&hash, sibling)
+            } else {
+                hash_pair(sibling, &hash)
+            };
+            index /= 2;
+        }
+        hash
+    }
+
+    /// Check the branch of `tx_id` against the merkle root of an 80-byte block header
+    pub fn verify(&self, tx_id: &[u8; 32], block_header: &[u8]) -> Result<bool> {
+        let block_header = BlockHeader::parse(block_header)?;
+        Ok(block_header.merkle_root == self.compute_root(tx_id))
+    }
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(left);
+    data[32..].copy_from_slice(right);
+    sha256d::Hash::hash(&data).to_byte_array()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        btc::hash_from_hex,
+        test_fixtures::{BLOCK_HEADER, MERKLE_PROOF, TX_ID, TX_INDEX},
+    };
+    #[test]
+    fn test_verify_branch_against_header() {
+        let header = hex::decode(BLOCK_HEADER).unwrap();
+        let tx_id = hash_from_hex(TX_ID).unwrap();
+        let branch = MerkleBranch {
+            tx_index: TX_INDEX,
+            hashes: (0..MERKLE_PROOF.len())
+                .step_by(64)
+                .map(|i| hash_from_hex(&MERKLE_PROOF[i..i + 64]).unwrap())
+                .collect(),
+        };
+
+        assert!(branch.verify(&tx_id, &header).unwrap());
+        assert!(!branch.verify(&[0; 32], &header).unwrap());
+        let wrong_index = MerkleBranch {
+            tx_index: TX_INDEX - 1,
+            ..branch.clone()
+        };
+        assert!(!wrong_index.verify(&tx_id, &header).unwrap());
+        assert!(branch.verify(&tx_id, &header[..79]).is_err());
+    }
+
+    #[test]
+    fn test_branch_from_tx_ids() {
+        let tx_ids: Vec<[u8; 32]> = (0..5u8).map(|i| [i; 32]).collect();
+        let root = {
+            let l1: Vec<[u8; 32]> = vec![
+                hash_pair(&tx_ids[0], &tx_ids[1]),
+                hash_pair(&tx_ids[2], &tx_ids[3]),
+                hash_pair(&tx_ids[4], &tx_ids[4]),
+            ];
+            let l2 = [hash_pair(&l1[0], &l1[1]), hash_pair(&l1[2], &l1[2])];
+            hash_pair(&l2[0], &l2[1])
+        };
+
+        for (index, tx_id) in tx_ids.iter().enumerate() {
+            let branch = MerkleBranch::from_tx_ids(&tx_ids, tx_id).unwrap();
+            assert_eq!(branch.tx_index as usize, index);
+            assert_eq!(branch.hashes.len(), 3);
+            assert_eq!(branch.compute_root(tx_id), root);
+        }
+
+        let single = MerkleBranch::from_tx_ids(&tx_ids[..1], &tx_ids[0]).unwrap();
+        assert!(single.hashes.is_empty());
+        assert_eq!(single.compute_root(&tx_ids[0]), tx_ids[0]);
+
+        assert!(MerkleBranch::from_tx_ids(&tx_ids, &[9; 32]).is_err());
+    }
+}
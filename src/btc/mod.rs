@@ -0,0 +1,45 @@
+//! Bitcoin helpers for building bridge inputs
+
+pub mod address;
+pub mod header;
+pub mod merkle;
+pub mod peg_in;
+pub mod rpc;
+pub mod script;
+pub mod transaction;
+
+pub use bitcoin::Network;
+
+use crate::error::{BridgeError, Result};
+
+/// Hex of a hash stored in internal byte order, reversed to the order shown by block explorers
+/// and Bitcoin Core
+pub fn hash_to_hex(hash: &[u8; 32]) -> String {
+    let mut hash = *hash;
+    hash.reverse();
+    hex::encode(hash)
+}
+
+/// Parse a hash displayed by block explorers and Bitcoin Core into internal byte order
+pub fn hash_from_hex(hash: &str) -> Result<[u8; 32]> {
+    let mut hash: [u8; 32] = hex::decode(hash)
+        .map_err(|e| BridgeError::Decoding(e.to_string()))?
+        .try_into()
+        .map_err(|_| BridgeError::Decoding("expected a 32 byte hash".to_string()))?;
+    hash.reverse();
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::TX_ID;
+
+    #[test]
+    fn test_hash_hex() {
+        let hash = hash_from_hex(TX_ID).unwrap();
+        assert_eq!(hash[0], 0x58);
+        assert_eq!(hash_to_hex(&hash), TX_ID);
+        assert!(hash_from_hex("00").is_err());
+    }
+}
@@ -0,0 +1,316 @@
+//! Peg-in proofs assembled from a Bitcoin Core compatible node
+
+use bitcoin::{consensus, hashes::Hash, BlockHash, MerkleBlock, Txid};
+use btc_light_client::instructions::verify_tx::BtcTxProof;
+use serde_json::{json, Value};
+
+use crate::{
+    btc::{
+        header::BlockHeader,
+        merkle::MerkleBranch,
+        rpc::{BitcoinRpcTransport, HttpTransport},
+        transaction::BtcTransaction,
+    },
+    error::{BridgeError, Result},
+};
+
+/// Proof of a peg-in output, ready for `verify_transaction`
+#[derive(Debug, Clone)]
+pub struct PegInProof {
+    /// Height of the block including the transaction
+    pub block_height: u64,
+    pub tx_proof: BtcTxProof,
+}
+
+/// Builds [`PegInProof`]s with `getrawtransaction`, `getblockheader`, `getblock` and
+/// `gettxoutproof`
+///
+/// The node must be able to look up confirmed transactions by id, e.g. with `-txindex`.
+pub struct PegInProofBuilder<T = HttpTransport> {
+    transport: T,
+}
+
+impl PegInProofBuilder<HttpTransport> {
+    /// Connect to a node over HTTP
+    pub fn new(url: &str) -> Self {
+        Self::with_transport(HttpTransport::new(url))
+    }
+}
+
+impl<T: BitcoinRpcTransport> PegInProofBuilder<T> {
+    pub fn with_transport(transport: T) -> Self {
+        Self { transport }
+    }
+
+    /// Build the proof that output `output_index` of the transaction `tx_id_hex` pays
+    /// `expected_amount` to `expected_script_hash`
+    ///
+    /// `tx_id_hex` is in the byte order shown by Bitcoin Core. The script hash of a bridge
+    /// deposit address is given by [`crate::btc::script::expected_script_hash_from_address`].
+    /// Fails with [`BridgeError::InvalidBtcTransaction`] if the output does not match, so no
+    /// proof is built for a transaction paying elsewhere.
+    pub async fn build(
+        &self,
+        tx_id_hex: &str,
+        output_index: u32,
+        expected_amount: u64,
+        expected_script_hash: &[u8; 32],
+    ) -> Result<PegInProof> {
+        let tx_id: Txid = tx_id_hex
+            .parse()
+            .map_err(|e| BridgeError::InvalidBtcTransaction(format!("{}: {}", tx_id_hex, e)))?;
+
+        let raw_tx = self
+            .transport
+            .call("getrawtransaction", vec![json!(tx_id_hex), json!(true)])
+            .await?;
+        let tx = BtcTransaction::from_hex(str_field(&raw_tx, "hex")?)?;
+        if tx.tx_id() != tx_id.to_byte_array() {
+            return Err(BridgeError::BitcoinRpc(format!(
+                "node returned transaction {} for {}",
+                tx.tx_id_hex(),
+                tx_id_hex
+            )));
+        }
+        tx.check_output(output_index, expected_amount, expected_script_hash)?;
+        let block_hash = str_field(&raw_tx, "blockhash").map_err(|_| {
+            BridgeError::InvalidBtcTransaction(format!("transaction {} is unconfirmed", tx_id_hex))
+        })?;
+
+        let header = self
+            .transport
+            .call("getblockheader", vec![json!(block_hash), json!(true)])
+            .await?;
+        let block_height = header["height"]
+            .as_u64()
+            .ok_or_else(|| BridgeError::BitcoinRpc("getblockheader: missing height".to_string()))?;
+
+        // Branch from the block's transaction list
+        let block = self
+            .transport
+            .call("getblock", vec![json!(block_hash), json!(1)])
+            .await?;
+        let tx_ids = block["tx"]
+            .as_array()
+            .ok_or_else(|| BridgeError::BitcoinRpc("getblock: missing tx".to_string()))?
+            .iter()
+            .map(|tx_id| {
+                tx_id
+                    .as_str()
+                    .and_then(|tx_id| tx_id.parse::<Txid>().ok())
+                    .map(|tx_id| tx_id.to_byte_array())
+                    .ok_or_else(|| BridgeError::BitcoinRpc("getblock: invalid txid".to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let branch = MerkleBranch::from_tx_ids(&tx_ids, &tx.tx_id())?;
+
+        // Header and index proven by the node, checked against the branch
+        let block_header = self
+            .tx_out_proof(&tx_id, block_hash, branch.tx_index)
+            .await?;
+        if !branch.verify(&tx.tx_id(), &block_header.serialize())? {
+            return Err(BridgeError::BitcoinRpc(format!(
+                "merkle branch of {} does not match block {}",
+                tx_id_hex, block_hash
+            )));
+        }
+
+        Ok(PegInProof {
+            block_height,
+            tx_proof: BtcTxProof {
+                block_header: block_header.serialize().to_vec(),
+                tx_id: tx.tx_id(),
+                tx_index: branch.tx_index,
+                merkle_proof: branch.hashes,
+                raw_tx: tx.raw_tx(),
+                output_index,
+                expected_amount,
+                expected_script_hash: *expected_script_hash,
+            },
+        })
+    }
+
+    /// Fetch and check `gettxoutproof` for block `block_hash`, returning the proven header
+    async fn tx_out_proof(
+        &self,
+        tx_id: &Txid,
+        block_hash: &str,
+        tx_index: u32,
+    ) -> Result<BlockHeader> {
+        let proof = self
+            .transport
+            .call(
+                "gettxoutproof",
+                vec![json!([tx_id.to_string()]), json!(block_hash)],
+            )
+            .await?;
+        let proof = proof
+            .as_str()
+            .and_then(|proof| hex::decode(proof).ok())
+            .ok_or_else(|| BridgeError::BitcoinRpc("gettxoutproof: invalid proof".to_string()))?;
+        let merkle_block: MerkleBlock = consensus::deserialize(&proof)
+            .map_err(|e| BridgeError::BitcoinRpc(format!("gettxoutproof: {}", e)))?;
+
+        // The height was looked up by block hash, the header must be of the same block
+        let expected_block_hash: BlockHash = block_hash.parse().map_err(|e| {
+            BridgeError::BitcoinRpc(format!("invalid block hash {}: {}", block_hash, e))
+        })?;
+        if merkle_block.header.block_hash() != expected_block_hash {
+            return Err(BridgeError::BitcoinRpc(format!(
+                "gettxoutproof returned block {} instead of {}",
+                merkle_block.header.block_hash(),
+                block_hash
+            )));
+        }
+
+        let mut matches = Vec::new();
+        let mut indexes = Vec::new();
+        merkle_block
+            .extract_matches(&mut matches, &mut indexes)
+            .map_err(|e| BridgeError::BitcoinRpc(format!("gettxoutproof: {}", e)))?;
+        if !matches
+            .iter()
+            .zip(&indexes)
+            .any(|(matched, index)| matched == tx_id && *index == tx_index)
+        {
+            return Err(BridgeError::BitcoinRpc(format!(
+                "gettxoutproof does not prove {} at index {}",
+                tx_id, tx_index
+            )));
+        }
+        Ok(merkle_block.header.into())
+    }
+}
+
+fn str_field<'a>(value: &'a Value, field: &str) -> Result<&'a str> {
+    value[field]
+        .as_str()
+        .ok_or_else(|| BridgeError::BitcoinRpc(format!("missing field {}", field)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::btc::rpc::BitcoinRpcTransport;
+    use crate::test_fixtures::{output_script_hash, OUTPUT_AMOUNT, RAW_TX};
+    use async_trait::async_trait;
+    use bitcoin::{
+        block::{Header, Version},
+        CompactTarget, TxMerkleNode,
+    };
+    use std::collections::HashMap;
+
+    /// Stand-in node answering each method with a canned result
+    struct MockTransport {
+        responses: HashMap<&'static str, Value>,
+    }
+
+    #[async_trait]
+    impl BitcoinRpcTransport for MockTransport {
+        async fn call(&self, method: &str, _params: Vec<Value>) -> Result<Value> {
+            self.responses
+                .get(method)
+                .cloned()
+                .ok_or_else(|| BridgeError::BitcoinRpc(format!("unexpected call {}", method)))
+        }
+    }
+
+    /// Node serving a block with the fixture transaction at index 2
+    ///
+    /// With `proof_time`, `gettxoutproof` answers for a block with another timestamp.
+    fn mock_node(tx: &BtcTransaction, proof_time: Option<u32>) -> (MockTransport, Header) {
+        let tx_ids: Vec<Txid> = vec![
+            Txid::from_byte_array([1; 32]),
+            Txid::from_byte_array([2; 32]),
+            tx.transaction().txid(),
+        ];
+        let tx_id_bytes: Vec<[u8; 32]> = tx_ids.iter().map(|id| id.to_byte_array()).collect();
+        let branch = MerkleBranch::from_tx_ids(&tx_id_bytes, &tx.tx_id()).unwrap();
+        let header = Header {
+            version: Version::TWO,
+            prev_blockhash: BlockHash::all_zeros(),
+            merkle_root: TxMerkleNode::from_byte_array(branch.compute_root(&tx.tx_id())),
+            time: 1_700_000_000,
+            bits: CompactTarget::from_consensus(0x207f_ffff),
+            nonce: 0,
+        };
+        let proof_header = Header {
+            time: proof_time.unwrap_or(header.time),
+            ..header
+        };
+        let merkle_block =
+            MerkleBlock::from_header_txids_with_predicate(&proof_header, &tx_ids, |id| {
+                *id == tx_ids[2]
+            });
+
+        let transport = MockTransport {
+            responses: HashMap::from([
+                (
+                    "getrawtransaction",
+                    json!({ "hex": RAW_TX, "blockhash": header.block_hash().to_string() }),
+                ),
+                ("getblockheader", json!({ "height": 120 })),
+                (
+                    "getblock",
+                    json!({ "tx": tx_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>() }),
+                ),
+                (
+                    "gettxoutproof",
+                    json!(hex::encode(consensus::serialize(&merkle_block))),
+                ),
+            ]),
+        };
+        (transport, header)
+    }
+
+    #[tokio::test]
+    async fn test_build_peg_in_proof() {
+        let tx = BtcTransaction::from_hex(RAW_TX).unwrap();
+        let script_hash = output_script_hash();
+        let (transport, header) = mock_node(&tx, None);
+        let builder = PegInProofBuilder::with_transport(transport);
+
+        let proof = builder
+            .build(&tx.tx_id_hex(), 0, OUTPUT_AMOUNT, &script_hash)
+            .await
+            .unwrap();
+        assert_eq!(proof.block_height, 120);
+        assert_eq!(proof.tx_proof.tx_id, tx.tx_id());
+        assert_eq!(proof.tx_proof.tx_index, 2);
+        assert_eq!(proof.tx_proof.block_header, consensus::serialize(&header));
+        assert_eq!(proof.tx_proof.expected_amount, OUTPUT_AMOUNT);
+        assert_eq!(proof.tx_proof.expected_script_hash, script_hash);
+        let branch = MerkleBranch {
+            tx_index: proof.tx_proof.tx_index,
+            hashes: proof.tx_proof.merkle_proof,
+        };
+        assert!(branch
+            .verify(&tx.tx_id(), &proof.tx_proof.block_header)
+            .unwrap());
+
+        // The output does not pay the expected amount or script
+        assert!(matches!(
+            builder
+                .build(&tx.tx_id_hex(), 0, OUTPUT_AMOUNT - 1, &script_hash)
+                .await,
+            Err(BridgeError::InvalidBtcTransaction(_))
+        ));
+        assert!(matches!(
+            builder
+                .build(&tx.tx_id_hex(), 1, OUTPUT_AMOUNT, &script_hash)
+                .await,
+            Err(BridgeError::InvalidBtcTransaction(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reject_proof_of_another_block() {
+        let tx = BtcTransaction::from_hex(RAW_TX).unwrap();
+        let (transport, _) = mock_node(&tx, Some(1_700_000_600));
+
+        let result = PegInProofBuilder::with_transport(transport)
+            .build(&tx.tx_id_hex(), 0, OUTPUT_AMOUNT, &output_script_hash())
+            .await;
+        assert!(matches!(result, Err(BridgeError::BitcoinRpc(_))));
+    }
+}
@@ -0,0 +1,250 @@
+//! Minimal Bitcoin Core JSON-RPC client
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::{
+    bridge_client::DEFAULT_RPC_TIMEOUT,
+    error::{BridgeError, Result},
+};
+
+/// Transport for Bitcoin Core JSON-RPC calls
+///
+/// Implement this to route calls through another client or to stub a node in tests.
+#[async_trait]
+pub trait BitcoinRpcTransport: Send + Sync {
+    /// Call `method` with `params` and return the `result` field of the response
+    async fn call(&self, method: &str, params: Vec<Value>) -> Result<Value>;
+}
+
+/// JSON-RPC over HTTP with optional basic authentication
+pub struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    auth: Option<(String, String)>,
+    timeout: Duration,
+}
+
+impl HttpTransport {
+    /// Transport to the node at `url`, requests time out after [`DEFAULT_RPC_TIMEOUT`]
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.to_string(),
+            auth: None,
+            timeout: DEFAULT_RPC_TIMEOUT,
+        }
+    }
+
+    /// Time out requests, including reading the response, after `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Authenticate with the node's `rpcuser` and `rpcpassword`
+    pub fn with_auth(mut self, user: &str, password: &str) -> Self {
+        self.auth = Some((user.to_string(), password.to_string()));
+        self
+    }
+}
+
+#[async_trait]
+impl BitcoinRpcTransport for HttpTransport {
+    async fn call(&self, method: &str, params: Vec<Value>) -> Result<Value> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .timeout(self.timeout)
+            .json(&json!({
+                "jsonrpc": "1.0",
+                "id": "solana-client-sdk",
+                "method": method,
+                "params": params,
+            }));
+        if let Some((user, password)) = &self.auth {
+            request = request.basic_auth(user, Some(password));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| transport_error(method, e))?;
+        let status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| transport_error(method, e))?;
+
+        // Bitcoin Core answers RPC errors with a non-2xx status and a JSON body, other failures
+        // such as a 401 for wrong credentials come without one
+        let response: Value = match serde_json::from_slice(&body) {
+            Ok(response) => response,
+            Err(_) if !status.is_success() => {
+                return Err(BridgeError::BitcoinRpc(format!(
+                    "{}: http status {}",
+                    method, status
+                )))
+            }
+            Err(e) => return Err(BridgeError::BitcoinRpc(format!("{}: {}", method, e))),
+        };
+        rpc_result(method, response)
+    }
+}
+
+fn transport_error(method: &str, error: reqwest::Error) -> BridgeError {
+    if error.is_timeout() {
+        return BridgeError::RpcTimeout;
+    }
+    BridgeError::BitcoinRpc(format!("{}: {}", method, error))
+}
+
+/// Extract the result of a JSON-RPC response
+fn rpc_result(method: &str, mut response: Value) -> Result<Value> {
+    match response.get("error") {
+        Some(error) if !error.is_null() => {
+            Err(BridgeError::BitcoinRpc(format!("{}: {}", method, error)))
+        }
+        _ => Ok(response["result"].take()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        task::JoinHandle,
+    };
+
+    /// Local stand-in node answering a single request with `status` and `body`
+    ///
+    /// Returns the node URL and a handle resolving to the raw request received.
+    async fn serve_once(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 4096];
+            loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+                    let content_length = headers
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|len| len.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= content_length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+
+            let response = format!(
+                "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_http_transport_request() {
+        let (url, request) = serve_once(
+            "200 OK",
+            r#"{"result":{"height":120},"error":null,"id":"solana-client-sdk"}"#,
+        )
+        .await;
+
+        let transport = HttpTransport::new(&url).with_auth("user", "password");
+        let result = transport
+            .call("getblockheader", vec![json!("00ff"), json!(true)])
+            .await
+            .unwrap();
+        assert_eq!(result, json!({ "height": 120 }));
+
+        let request = request.await.unwrap();
+        let (headers, body) = request.split_once("\r\n\r\n").unwrap();
+        assert!(headers.starts_with("POST / HTTP/1.1"));
+        let authorization = format!("authorization: Basic {}", STANDARD.encode("user:password"));
+        assert!(headers
+            .lines()
+            .any(|line| line.eq_ignore_ascii_case(&authorization)));
+        let body: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["jsonrpc"], "1.0");
+        assert_eq!(body["method"], "getblockheader");
+        assert_eq!(body["params"], json!(["00ff", true]));
+    }
+
+    #[tokio::test]
+    async fn test_http_transport_errors() {
+        // Bitcoin Core reports RPC errors with a 500 and a JSON body
+        let (url, _) = serve_once(
+            "500 Internal Server Error",
+            r#"{"result":null,"error":{"code":-5,"message":"No such mempool or blockchain transaction"},"id":"solana-client-sdk"}"#,
+        )
+        .await;
+        let error = HttpTransport::new(&url)
+            .call("getrawtransaction", vec![])
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("No such mempool"));
+
+        // Wrong credentials are rejected with an empty body
+        let (url, _) = serve_once("401 Unauthorized", "").await;
+        let error = HttpTransport::new(&url)
+            .call("getrawtransaction", vec![])
+            .await
+            .unwrap_err();
+        assert!(matches!(error, BridgeError::BitcoinRpc(_)));
+        assert!(error.to_string().contains("401"));
+
+        // Node accepting the connection but never answering
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let _node = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            drop(stream);
+        });
+        let error = HttpTransport::new(&url)
+            .with_timeout(Duration::from_millis(100))
+            .call("getblockcount", vec![])
+            .await
+            .unwrap_err();
+        assert!(error.is_timeout());
+    }
+
+    #[test]
+    fn test_rpc_result() {
+        let response = json!({ "result": 100, "error": null, "id": "solana-client-sdk" });
+        assert_eq!(rpc_result("getblockcount", response).unwrap(), json!(100));
+
+        let response = json!({
+            "result": null,
+            "error": { "code": -5, "message": "No such mempool or blockchain transaction" },
+        });
+        assert!(matches!(
+            rpc_result("getrawtransaction", response),
+            Err(BridgeError::BitcoinRpc(_))
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+//! Expected script hash of peg-in outputs
+//!
+//! The light client compares `BtcTxProof::expected_script_hash` with the 32-byte witness
+//! program of the verified output: the SHA-256 of the witness script for P2WSH and the
+//! x-only output key for P2TR.
+
+use bitcoin::{
+    hashes::{sha256, Hash},
+    Network, Script,
+};
+
+use crate::{
+    btc::address::parse_btc_address,
+    error::{BridgeError, Result},
+};
+
+/// Expected script hash of an output locked by `script_pubkey`, which must be P2WSH or P2TR
+pub fn expected_script_hash_from_script(script_pubkey: &[u8]) -> Result<[u8; 32]> {
+    let script = Script::from_bytes(script_pubkey);
+    if !script.is_p2wsh() && !script.is_p2tr() {
+        return Err(BridgeError::InvalidBtcTransaction(format!(
+            "script {} is not P2WSH or P2TR",
+            hex::encode(script_pubkey)
+        )));
+    }
+    // Both are a version opcode, a 32-byte push and the witness program
+    let mut program = [0u8; 32];
+    program.copy_from_slice(&script_pubkey[2..]);
+    Ok(program)
+}
+
+/// Expected script hash of an output paying a P2WSH or P2TR `address`
+///
+/// The address is checked against `network` when given.
+pub fn expected_script_hash_from_address(
+    address: &str,
+    network: Option<Network>,
+) -> Result<[u8; 32]> {
+    let address = parse_btc_address(address, network)?;
+    expected_script_hash_from_script(address.script_pubkey().as_bytes())
+}
+
+/// Expected script hash of a P2WSH output spending to `witness_script`
+pub fn expected_script_hash_from_witness_script(witness_script: &[u8]) -> [u8; 32] {
+    sha256::Hash::hash(witness_script).to_byte_array()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{output_script_hash, OUTPUT_SCRIPT_PUBKEY};
+    use bitcoin::{Address, ScriptBuf};
+
+    #[test]
+    fn test_expected_script_hash() {
+        let script_pubkey = hex::decode(OUTPUT_SCRIPT_PUBKEY).unwrap();
+        let expected = output_script_hash();
+        assert_eq!(
+            expected_script_hash_from_script(&script_pubkey).unwrap(),
+            expected
+        );
+
+        let address =
+            Address::from_script(Script::from_bytes(&script_pubkey), Network::Testnet).unwrap();
+        assert_eq!(
+            expected_script_hash_from_address(&address.to_string(), Some(Network::Testnet))
+                .unwrap(),
+            expected
+        );
+
+        let p2tr = "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr";
+        assert!(expected_script_hash_from_address(p2tr, Some(Network::Bitcoin)).is_ok());
+        let p2wpkh = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+        assert!(expected_script_hash_from_address(p2wpkh, None).is_err());
+
+        let witness_script = ScriptBuf::from_bytes(vec![0x51]);
+        let p2wsh = ScriptBuf::new_p2wsh(&witness_script.wscript_hash());
+        assert_eq!(
+            expected_script_hash_from_witness_script(witness_script.as_bytes()),
+            expected_script_hash_from_script(p2wsh.as_bytes()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Bitcoin transaction parsing for `verify_transaction` inputs
+
+use bitcoin::{consensus, hashes::Hash, Transaction};
+
+use crate::{
+    btc::{hash_to_hex, script::expected_script_hash_from_script},
+    error::{BridgeError, Result},
+};
+
+/// Output of a Bitcoin transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOutputInfo {
+    /// Index of the output in the transaction
+    pub index: u32,
+    /// Amount in satoshis
+    pub amount: u64,
+    /// Locking script of the output
+    pub script_pubkey: Vec<u8>,
+}
+
+/// Parsed Bitcoin transaction, legacy or segwit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BtcTransaction {
+    tx: Transaction,
+}
+
+impl BtcTransaction {
+    /// Parse a consensus serialized transaction, with or without witness data
+    pub fn from_bytes(raw_tx: &[u8]) -> Result<Self> {
+        let tx = consensus::deserialize(raw_tx)
+            .map_err(|e| BridgeError::InvalidBtcTransaction(e.to_string()))?;
+        Ok(Self { tx })
+    }
+
+    /// Parse a hex encoded transaction, as returned by `getrawtransaction`
+    pub fn from_hex(raw_tx: &str) -> Result<Self> {
+        let raw_tx =
+            hex::decode(raw_tx).map_err(|e| BridgeError::InvalidBtcTransaction(e.to_string()))?;
+        Self::from_bytes(&raw_tx)
+    }
+
+    pub fn transaction(&self) -> &Transaction {
+        &self.tx
+    }
+
+    /// Transaction id in internal byte order, as expected by `BtcTxProof::tx_id`
+    pub fn tx_id(&self) -> [u8; 32] {
+        self.tx.txid().to_byte_array()
+    }
+
+    /// Hex of [`Self::tx_id`], see [`hash_to_hex`]
+    pub fn tx_id_hex(&self) -> String {
+        hash_to_hex(&self.tx_id())
+    }
+
+    /// Serialization without witness data, which is what the txid commits to and what the
+    /// light client hashes from `BtcTxProof::raw_tx`
+    pub fn raw_tx(&self) -> Vec<u8> {
+        let mut tx = self.tx.clone();
+        for input in &mut tx.input {
+            input.witness.clear();
+        }
+        consensus::serialize(&tx)
+    }
+
+    pub fn outputs(&self) -> Vec<TxOutputInfo> {
+        self.tx
+            .output
+            .iter()
+            .enumerate()
+            .map(|(index, output)| TxOutputInfo {
+                index: index as u32,
+                amount: output.value.to_sat(),
+                script_pubkey: output.script_pubkey.to_bytes(),
+            })
+            .collect()
+    }
+
+    pub fn output(&self, index: u32) -> Result<TxOutputInfo> {
+        self.outputs()
+            .into_iter()
+            .nth(index as usize)
+            .ok_or_else(|| {
+                BridgeError::InvalidBtcTransaction(format!(
+                    "output {} not found in {} outputs",
+                    index,
+                    self.tx.output.len()
+                ))
+            })
+    }
+
+    /// Check that output `output_index` pays `expected_amount` satoshis to the script with
+    /// `expected_script_hash`, as the light client does when verifying the proof
+    pub fn check_output(
+        &self,
+        output_index: u32,
+        expected_amount: u64,
+        expected_script_hash: &[u8; 32],
+    ) -> Result<()> {
+        let output = self.output(output_index)?;
+        if output.amount != expected_amount {
+            return Err(BridgeError::InvalidBtcTransaction(format!(
+                "output {} pays {} sats, expected {}",
+                output_index, output.amount, expected_amount
+            )));
+        }
+        if expected_script_hash_from_script(&output.script_pubkey)? != *expected_script_hash {
+            return Err(BridgeError::InvalidBtcTransaction(format!(
+                "output {} does not pay to script hash {}",
+                output_index,
+                hex::encode(expected_script_hash)
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        btc::hash_from_hex,
+        test_fixtures::{output_script_hash, OUTPUT_AMOUNT, OUTPUT_SCRIPT_PUBKEY, RAW_TX, TX_ID},
+    };
+
+    #[test]
+    fn test_parse_transaction() {
+        let tx = BtcTransaction::from_hex(RAW_TX).unwrap();
+        assert_eq!(tx.tx_id_hex(), TX_ID);
+        assert_eq!(tx.tx_id(), hash_from_hex(TX_ID).unwrap());
+        assert_eq!(hex::encode(tx.raw_tx()), RAW_TX);
+
+        let outputs = tx.outputs();
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].amount, OUTPUT_AMOUNT);
+        assert_eq!(hex::encode(&outputs[0].script_pubkey), OUTPUT_SCRIPT_PUBKEY);
+        assert_eq!(tx.output(1).unwrap().index, 1);
+        assert!(tx.output(2).is_err());
+
+        let script_hash = output_script_hash();
+        assert!(tx.check_output(0, OUTPUT_AMOUNT, &script_hash).is_ok());
+        assert!(tx.check_output(0, 400_000, &script_hash).is_err());
+        assert!(tx.check_output(1, 896_144_586, &script_hash).is_err());
+    }
+
+    #[test]
+    fn test_segwit_transaction_strips_witness() {
+        let mut tx = BtcTransaction::from_hex(RAW_TX).unwrap().tx;
+        tx.input[0].witness.push([0x01; 64]);
+        let segwit = BtcTransaction::from_bytes(&consensus::serialize(&tx)).unwrap();
+
+        assert_eq!(segwit.tx_id_hex(), TX_ID);
+        assert_eq!(hex::encode(segwit.raw_tx()), RAW_TX);
+    }
+}
@@ -0,0 +1,128 @@
+use anchor_client::solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, pubkey::Pubkey,
+};
+
+/// Compute unit limit requested for `verify_transaction` when none is configured
+pub const VERIFY_TX_COMPUTE_UNIT_LIMIT: u32 = 500_000;
+
+/// Percentile of recent prioritization fees used by [`PriorityFee::auto`]
+pub const DEFAULT_PRIORITY_FEE_PERCENTILE: u8 = 75;
+
+/// Upper bound in micro-lamports applied by [`PriorityFee::auto`]
+pub const DEFAULT_MAX_COMPUTE_UNIT_PRICE: u64 = 1_000_000;
+
+/// How the compute unit price of a bridge transaction is chosen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PriorityFee {
+    /// Send without a compute unit price instruction
+    #[default]
+    None,
+    /// Fixed compute unit price in micro-lamports
+    Fixed(u64),
+    /// Derive the price from `getRecentPrioritizationFees` for the writable accounts
+    /// of the transaction, capped at `max_price` micro-lamports
+    Auto { percentile: u8, max_price: u64 },
+}
+
+impl PriorityFee {
+    pub fn auto() -> Self {
+        PriorityFee::Auto {
+            percentile: DEFAULT_PRIORITY_FEE_PERCENTILE,
+            max_price: DEFAULT_MAX_COMPUTE_UNIT_PRICE,
+        }
+    }
+}
+
+/// Compute budget settings prepended to bridge transactions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComputeBudgetConfig {
+    /// Compute unit limit, `None` keeps the runtime default
+    pub compute_unit_limit: Option<u32>,
+    /// Compute unit price selection
+    pub priority_fee: PriorityFee,
+}
+
+impl ComputeBudgetConfig {
+    pub fn new(compute_unit_limit: Option<u32>, priority_fee: PriorityFee) -> Self {
+        Self {
+            compute_unit_limit,
+            priority_fee,
+        }
+    }
+
+    /// Build the compute budget instructions
+    ///
+    /// `default_limit` is used when no limit is configured and `compute_unit_price` is the
+    /// price resolved from [`Self::priority_fee`].
+    pub fn instructions(
+        &self,
+        default_limit: Option<u32>,
+        compute_unit_price: Option<u64>,
+    ) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        if let Some(limit) = self.compute_unit_limit.or(default_limit) {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+        }
+        if let Some(price) = compute_unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        instructions
+    }
+}
+
+/// Writable accounts of `instructions`, used to query recent prioritization fees
+pub fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = Vec::new();
+    for meta in instructions.iter().flat_map(|ix| ix.accounts.iter()) {
+        if meta.is_writable && !accounts.contains(&meta.pubkey) {
+            accounts.push(meta.pubkey);
+        }
+    }
+    accounts
+}
+
+/// Pick the `percentile` of recent prioritization fees, capped at `max_price`
+pub fn compute_unit_price_from_recent_fees(fees: &[u64], percentile: u8, max_price: u64) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    let mut fees = fees.to_vec();
+    fees.sort_unstable();
+    let index = (fees.len() - 1) * usize::from(percentile.min(100)) / 100;
+    fees[index].min(max_price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_unit_price_from_recent_fees() {
+        assert_eq!(compute_unit_price_from_recent_fees(&[], 75, 1_000), 0);
+        let fees = [0, 10, 500, 20, 30];
+        assert_eq!(compute_unit_price_from_recent_fees(&fees, 0, 1_000), 0);
+        assert_eq!(compute_unit_price_from_recent_fees(&fees, 50, 1_000), 20);
+        assert_eq!(compute_unit_price_from_recent_fees(&fees, 75, 1_000), 30);
+        assert_eq!(compute_unit_price_from_recent_fees(&fees, 100, 1_000), 500);
+        assert_eq!(compute_unit_price_from_recent_fees(&fees, 100, 100), 100);
+    }
+
+    #[test]
+    fn test_compute_budget_instructions() {
+        let config = ComputeBudgetConfig::default();
+        assert!(config.instructions(None, None).is_empty());
+        assert_eq!(
+            config.instructions(Some(VERIFY_TX_COMPUTE_UNIT_LIMIT), Some(5)),
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(VERIFY_TX_COMPUTE_UNIT_LIMIT),
+                ComputeBudgetInstruction::set_compute_unit_price(5),
+            ]
+        );
+
+        let config = ComputeBudgetConfig::new(Some(200_000), PriorityFee::Fixed(5));
+        assert_eq!(
+            config.instructions(Some(VERIFY_TX_COMPUTE_UNIT_LIMIT), None),
+            vec![ComputeBudgetInstruction::set_compute_unit_limit(200_000)]
+        );
+    }
+}
@@ -0,0 +1,607 @@
+use anchor_client::{
+    solana_client::{
+        client_error::{ClientError, ClientErrorKind},
+        rpc_request::{RpcError, RpcResponseErrorData},
+    },
+    solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        pubkey::{ParsePubkeyError, Pubkey},
+        signature::ParseSignatureError,
+        signer::SignerError,
+        transaction::TransactionError,
+    },
+};
+use bitvm_bridge::errors::BitvmBridgeError;
+use btc_light_client::errors::BtcLightClientError;
+use thiserror::Error;
+
+use crate::{
+    btc::address::BtcAddressError,
+    preflight::{BurnRejection, MintRejection},
+};
+
+pub type Result<T> = std::result::Result<T, BridgeError>;
+
+/// Errors returned by the SDK
+#[derive(Debug, Error)]
+pub enum BridgeError {
+    #[error("invalid pubkey: {0}")]
+    InvalidPubkey(#[from] ParsePubkeyError),
+
+    #[error("invalid signature: {0}")]
+    InvalidSignature(#[from] ParseSignatureError),
+
+    #[error("invalid keypair: {0}")]
+    InvalidKeypair(String),
+
+    #[error("invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("decoding error: {0}")]
+    Decoding(String),
+
+    #[error("rpc request timed out")]
+    RpcTimeout,
+
+    #[error("rpc error: {0}")]
+    Rpc(#[source] Box<ClientError>),
+
+    #[error("invalid bitcoin address: {0}")]
+    InvalidBtcAddress(#[from] BtcAddressError),
+
+    #[error("invalid bitcoin transaction: {0}")]
+    InvalidBtcTransaction(String),
+
+    #[error("invalid block header: {0}")]
+    InvalidBlockHeader(String),
+
+    #[error("bitcoin rpc error: {0}")]
+    BitcoinRpc(String),
+
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+
+    #[error(
+        "timed out waiting for block {block_height}: {confirmations} of {min_confirmations} confirmations"
+    )]
+    ConfirmationTimeout {
+        block_height: u64,
+        confirmations: u64,
+        min_confirmations: u64,
+    },
+
+    #[error("{0}")]
+    Program(ProgramError),
+
+    #[error("mint rejected: {0}")]
+    MintRejected(#[from] MintRejection),
+
+    #[error("burn rejected: {0}")]
+    BurnRejected(#[from] BurnRejection),
+
+    #[error("transaction error: {0}")]
+    Transaction(#[from] TransactionError),
+
+    #[error("signer error: {0}")]
+    Signer(#[from] SignerError),
+
+    #[error("anchor client error: {0}")]
+    Anchor(#[source] Box<anchor_client::ClientError>),
+
+    #[error("event handler error: {0}")]
+    EventHandler(#[source] anyhow::Error),
+}
+
+/// Declare the variants of an `#[error_code]` enum, which cannot be iterated, in declaration
+/// order. The list is also matched exhaustively so a variant added to the enum fails to
+/// compile until it is listed here.
+macro_rules! error_table {
+    ($table:ident, $guard:ident, $error:ident { $($variant:ident),* $(,)? }) => {
+        const $table: &[$error] = &[$($error::$variant),*];
+
+        #[allow(dead_code)]
+        const fn $guard(error: $error) {
+            match error {
+                $($error::$variant => {})*
+            }
+        }
+    };
+}
+
+error_table!(
+    BITVM_BRIDGE_ERRORS,
+    bitvm_bridge_errors_are_listed,
+    BitvmBridgeError {
+        Unauthorized,
+        TxAlreadyMinted,
+        TxNotVerified,
+        InvalidMintAmount,
+        InvalidBurnAmount,
+        BurnPaused,
+        InvalidBtcAddress,
+    }
+);
+
+error_table!(
+    BTC_LIGHT_CLIENT_ERRORS,
+    btc_light_client_errors_are_listed,
+    BtcLightClientError {
+        Unauthorized,
+        InvalidBlockHeader,
+        BlockNotFound,
+        InsufficientConfirmations,
+        InvalidMerkleProof,
+        InvalidTransaction,
+        InvalidOutputIndex,
+        AmountMismatch,
+        ScriptHashMismatch,
+    }
+);
+
+/// Variant of the `#[error_code]` enum of one of the bridge programs
+#[derive(Debug, Clone, Copy)]
+pub enum BridgeProgramError {
+    BitvmBridge(BitvmBridgeError),
+    BtcLightClient(BtcLightClientError),
+}
+
+impl BridgeProgramError {
+    /// Look up the variant of the bitvm bridge error enum with custom error `code`
+    pub fn bitvm_bridge(code: u32) -> Option<Self> {
+        BITVM_BRIDGE_ERRORS
+            .iter()
+            .find(|error| u32::from(**error) == code)
+            .map(|error| BridgeProgramError::BitvmBridge(*error))
+    }
+
+    /// Look up the variant of the light client error enum with custom error `code`
+    pub fn btc_light_client(code: u32) -> Option<Self> {
+        BTC_LIGHT_CLIENT_ERRORS
+            .iter()
+            .find(|error| u32::from(**error) == code)
+            .map(|error| BridgeProgramError::BtcLightClient(*error))
+    }
+
+    /// Custom error code returned by the program
+    pub fn code(&self) -> u32 {
+        match self {
+            BridgeProgramError::BitvmBridge(error) => (*error).into(),
+            BridgeProgramError::BtcLightClient(error) => (*error).into(),
+        }
+    }
+
+    /// Name of the enum variant
+    pub fn name(&self) -> String {
+        match self {
+            BridgeProgramError::BitvmBridge(error) => error.name(),
+            BridgeProgramError::BtcLightClient(error) => error.name(),
+        }
+    }
+}
+
+impl PartialEq for BridgeProgramError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (BridgeProgramError::BitvmBridge(a), BridgeProgramError::BitvmBridge(b)) => {
+                u32::from(*a) == u32::from(*b)
+            }
+            (BridgeProgramError::BtcLightClient(a), BridgeProgramError::BtcLightClient(b)) => {
+                u32::from(*a) == u32::from(*b)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for BridgeProgramError {}
+
+impl std::fmt::Display for BridgeProgramError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BridgeProgramError::BitvmBridge(error) => error.fmt(f),
+            BridgeProgramError::BtcLightClient(error) => error.fmt(f),
+        }
+    }
+}
+
+impl From<BitvmBridgeError> for BridgeProgramError {
+    fn from(error: BitvmBridgeError) -> Self {
+        BridgeProgramError::BitvmBridge(error)
+    }
+}
+
+impl From<BtcLightClientError> for BridgeProgramError {
+    fn from(error: BtcLightClientError) -> Self {
+        BridgeProgramError::BtcLightClient(error)
+    }
+}
+
+/// Custom error returned by an on-chain program
+///
+/// Errors of the `bitvm_bridge` and `btc_light_client` programs are looked up by program id
+/// and code in the programs' `#[error_code]` enums with [`Self::decode`], so they are decoded
+/// even when the transaction logs are not available.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramError {
+    /// Program that failed, if the failing instruction is known
+    pub program_id: Option<Pubkey>,
+    /// Index of the failing instruction in the transaction
+    pub instruction_index: u8,
+    /// Custom error code, Anchor user errors start at 6000
+    pub code: u32,
+    /// Error enum variant, if the error was raised by one of the bridge programs
+    pub error: Option<BridgeProgramError>,
+    /// Name of the error enum variant, from the error enum or the logs
+    pub name: Option<String>,
+    /// Human readable error message, from the logs or the error enum
+    pub message: Option<String>,
+    /// Program logs of the failing instruction
+    pub logs: Vec<String>,
+}
+
+impl ProgramError {
+    /// Parse a custom program error from a transaction error and the transaction logs
+    ///
+    /// The error enum variant is not looked up, see [`Self::decode`].
+    pub fn from_transaction_error(error: &TransactionError, logs: &[String]) -> Option<Self> {
+        let TransactionError::InstructionError(instruction_index, InstructionError::Custom(code)) =
+            error
+        else {
+            return None;
+        };
+
+        let logs = instruction_logs(logs, *instruction_index);
+        let program_id = logs.first().and_then(|log| invoked_program(log));
+        let (name, message) = logs
+            .iter()
+            .find_map(|log| parse_anchor_error_log(log))
+            .filter(|(_, number, _)| number == code)
+            .map(|(name, _, message)| (Some(name), Some(message)))
+            .unwrap_or_default();
+
+        Some(Self {
+            program_id,
+            instruction_index: *instruction_index,
+            code: *code,
+            error: None,
+            name,
+            message,
+            logs,
+        })
+    }
+
+    /// Look up the error enum variant of the failing program, given the program ids of the
+    /// bridge deployment. The name and message parsed from the logs are kept for codes missing
+    /// from the error enums.
+    pub fn decode(
+        &mut self,
+        bitvm_bridge_program_id: &Pubkey,
+        btc_light_client_program_id: &Pubkey,
+    ) {
+        self.error = match self.program_id {
+            Some(program_id) if program_id == *bitvm_bridge_program_id => {
+                BridgeProgramError::bitvm_bridge(self.code)
+            }
+            Some(program_id) if program_id == *btc_light_client_program_id => {
+                BridgeProgramError::btc_light_client(self.code)
+            }
+            _ => None,
+        };
+        if let Some(error) = self.error {
+            self.name = Some(error.name());
+            if self.message.is_none() {
+                self.message = Some(error.to_string());
+            }
+        }
+    }
+
+    /// Check the error against a variant of a bridge program's error enum
+    pub fn is(&self, error: impl Into<BridgeProgramError>) -> bool {
+        self.error == Some(error.into())
+    }
+}
+
+impl std::fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.program_id {
+            Some(program_id) => write!(
+                f,
+                "program {} failed with custom error {:#x} in instruction {}",
+                program_id, self.code, self.instruction_index
+            )?,
+            None => write!(
+                f,
+                "custom program error {:#x} in instruction {}",
+                self.code, self.instruction_index
+            )?,
+        }
+        match (&self.name, &self.message) {
+            (Some(name), Some(message)) => write!(f, " ({}: {})", name, message),
+            (None, Some(message)) => write!(f, " ({})", message),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Logs emitted by the top-level instruction at `instruction_index`
+fn instruction_logs(logs: &[String], instruction_index: u8) -> Vec<String> {
+    let mut current: Option<usize> = None;
+    let mut instruction_logs = Vec::new();
+    for log in logs {
+        if log.starts_with("Program ") && log.ends_with(" invoke [1]") {
+            current = Some(current.map_or(0, |index| index + 1));
+        }
+        if current == Some(usize::from(instruction_index)) {
+            instruction_logs.push(log.clone());
+        }
+    }
+    instruction_logs
+}
+
+/// Program id of a `Program <id> invoke [n]` log
+fn invoked_program(log: &str) -> Option<Pubkey> {
+    let program = log.strip_prefix("Program ")?.split(' ').next()?;
+    program.parse().ok()
+}
+
+/// Parse the error name, number and message of an Anchor error log
+/// (`Error Code: <name>. Error Number: <number>. Error Message: <message>.`)
+fn parse_anchor_error_log(log: &str) -> Option<(String, u32, String)> {
+    let (_, rest) = log.split_once("Error Code: ")?;
+    let (name, rest) = rest.split_once(". Error Number: ")?;
+    let (number, message) = rest.split_once(". Error Message: ")?;
+    Some((
+        name.to_string(),
+        number.parse().ok()?,
+        message.trim_end_matches('.').to_string(),
+    ))
+}
+
+impl BridgeError {
+    /// Convert an RPC error raised while sending or simulating `instructions`, attributing
+    /// custom program errors to the program of the failing instruction
+    ///
+    /// See [`ProgramError::decode`] to look up the error enum variant.
+    pub fn from_client_error(error: ClientError, instructions: &[Instruction]) -> Self {
+        let program_ids: Vec<Pubkey> = instructions.iter().map(|ix| ix.program_id).collect();
+        Self::from(error).attribute_program_error(&program_ids)
+    }
+
+    /// Attribute a program error to the program of the failing instruction, `program_ids`
+    /// holding the program id of each instruction of the transaction
+    pub(crate) fn attribute_program_error(mut self, program_ids: &[Pubkey]) -> Self {
+        if let BridgeError::Program(program_error) = &mut self {
+            if let Some(program_id) = program_ids.get(usize::from(program_error.instruction_index))
+            {
+                program_error.program_id = Some(*program_id);
+            }
+        }
+        self
+    }
+
+    /// Decode a program error against the program ids of a bridge deployment
+    pub(crate) fn decode_program_error(
+        mut self,
+        bitvm_bridge_program_id: &Pubkey,
+        btc_light_client_program_id: &Pubkey,
+    ) -> Self {
+        if let BridgeError::Program(program_error) = &mut self {
+            program_error.decode(bitvm_bridge_program_id, btc_light_client_program_id);
+        }
+        self
+    }
+
+    /// Decoded program error, if the error was raised by an on-chain program
+    pub fn program_error(&self) -> Option<&ProgramError> {
+        match self {
+            BridgeError::Program(program_error) => Some(program_error),
+            _ => None,
+        }
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, BridgeError::RpcTimeout)
+    }
+
+    /// Custom program error code, if the error was raised by an on-chain program
+    pub fn program_error_code(&self) -> Option<u32> {
+        match self {
+            BridgeError::Program(program_error) => Some(program_error.code),
+            _ => None,
+        }
+    }
+}
+
+impl From<ClientError> for BridgeError {
+    fn from(error: ClientError) -> Self {
+        if let ClientErrorKind::Reqwest(reqwest_error) = error.kind() {
+            if reqwest_error.is_timeout() {
+                return BridgeError::RpcTimeout;
+            }
+        }
+        if let Some(transaction_error) = error.get_transaction_error() {
+            let logs = match error.kind() {
+                ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                    data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+                    ..
+                }) => result.logs.clone().unwrap_or_default(),
+                _ => Vec::new(),
+            };
+            if let Some(program_error) =
+                ProgramError::from_transaction_error(&transaction_error, &logs)
+            {
+                return BridgeError::Program(program_error);
+            }
+        }
+        BridgeError::Rpc(Box::new(error))
+    }
+}
+
+impl From<anchor_client::ClientError> for BridgeError {
+    fn from(error: anchor_client::ClientError) -> Self {
+        match error {
+            anchor_client::ClientError::SolanaClientError(error) => error.into(),
+            error => BridgeError::Anchor(Box::new(error)),
+        }
+    }
+}
+
+impl From<anchor_client::anchor_lang::error::Error> for BridgeError {
+    fn from(error: anchor_client::anchor_lang::error::Error) -> Self {
+        BridgeError::Decoding(error.to_string())
+    }
+}
+
+impl From<bs58::decode::Error> for BridgeError {
+    fn from(error: bs58::decode::Error) -> Self {
+        BridgeError::Decoding(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction;
+
+    #[test]
+    fn test_decode_anchor_program_error() {
+        let program_id = bitvm_bridge::ID;
+        let already_minted = BitvmBridgeError::TxAlreadyMinted;
+        let logs = vec![
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]".to_string(),
+            "Program ComputeBudget111111111111111111111111111111 success".to_string(),
+            format!("Program {} invoke [1]", program_id),
+            "Program log: Instruction: Mint".to_string(),
+            format!(
+                "Program log: AnchorError thrown in programs/bitvm-bridge/src/instructions/mint.rs:42. Error Code: {}. Error Number: {}. Error Message: {}.",
+                already_minted.name(),
+                u32::from(already_minted),
+                already_minted
+            ),
+            format!("Program {} consumed 5000 of 200000 compute units", program_id),
+            format!(
+                "Program {} failed: custom program error: {:#x}",
+                program_id,
+                u32::from(already_minted)
+            ),
+        ];
+        let error =
+            TransactionError::InstructionError(1, InstructionError::Custom(already_minted.into()));
+
+        let mut program_error = ProgramError::from_transaction_error(&error, &logs).unwrap();
+        assert_eq!(program_error.program_id, Some(program_id));
+        assert_eq!(program_error.code, u32::from(already_minted));
+        assert_eq!(program_error.name, Some(already_minted.name()));
+        assert_eq!(program_error.error, None);
+        program_error.decode(&program_id, &btc_light_client::ID);
+        assert!(program_error.is(already_minted));
+        assert_eq!(program_error.name, Some(already_minted.name()));
+        assert_eq!(program_error.message, Some(already_minted.to_string()));
+        assert_eq!(program_error.logs.len(), 5);
+
+        // Code missing from the error enum, the name and message come from the logs
+        let logs: Vec<String> = logs
+            .iter()
+            .map(|log| log.replace(&u32::from(already_minted).to_string(), "6100"))
+            .collect();
+        let error = TransactionError::InstructionError(1, InstructionError::Custom(6100));
+        let mut program_error = ProgramError::from_transaction_error(&error, &logs).unwrap();
+        program_error.decode(&program_id, &btc_light_client::ID);
+        assert_eq!(program_error.error, None);
+        assert_eq!(program_error.name, Some(already_minted.name()));
+        assert_eq!(program_error.message, Some(already_minted.to_string()));
+
+        // Unknown code without logs
+        let error = TransactionError::InstructionError(1, InstructionError::Custom(1));
+        let mut program_error = ProgramError::from_transaction_error(&error, &logs).unwrap();
+        program_error.decode(&program_id, &btc_light_client::ID);
+        assert_eq!(program_error.error, None);
+        assert_eq!(program_error.name, None);
+        assert_eq!(program_error.message, None);
+
+        assert!(
+            ProgramError::from_transaction_error(&TransactionError::AccountInUse, &logs).is_none()
+        );
+    }
+
+    #[test]
+    fn test_error_source() {
+        let error = BridgeError::from(ClientError::from(TransactionError::AccountNotFound));
+        assert!(std::error::Error::source(&error).is_some());
+
+        let error = BridgeError::EventHandler(anyhow::anyhow!("handler failed"));
+        assert_eq!(
+            std::error::Error::source(&error).unwrap().to_string(),
+            "handler failed"
+        );
+    }
+
+    #[test]
+    fn test_program_error_attribution() {
+        let program_id = btc_light_client::ID;
+        let invalid_proof = BtcLightClientError::InvalidMerkleProof;
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(500_000),
+            Instruction::new_with_bytes(program_id, &[], vec![]),
+        ];
+        let error = || {
+            ClientError::from(TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(invalid_proof.into()),
+            ))
+        };
+
+        // Decoded without logs, from the program id and the code
+        match BridgeError::from_client_error(error(), &instructions)
+            .decode_program_error(&bitvm_bridge::ID, &program_id)
+        {
+            BridgeError::Program(program_error) => {
+                assert_eq!(program_error.program_id, Some(program_id));
+                assert_eq!(program_error.instruction_index, 1);
+                assert!(program_error.is(invalid_proof));
+                assert_eq!(program_error.name, Some(invalid_proof.name()));
+                assert_eq!(program_error.message, Some(invalid_proof.to_string()));
+            }
+            error => panic!("unexpected error {:?}", error),
+        }
+
+        // Light client deployed at another address
+        let light_client_id = Pubkey::new_unique();
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(500_000),
+            Instruction::new_with_bytes(light_client_id, &[], vec![]),
+        ];
+        let error = BridgeError::from_client_error(error(), &instructions)
+            .decode_program_error(&bitvm_bridge::ID, &btc_light_client::ID);
+        assert_eq!(error.program_error().unwrap().error, None);
+        let error = error.decode_program_error(&bitvm_bridge::ID, &light_client_id);
+        assert!(error.program_error().unwrap().is(invalid_proof));
+
+        let error = ClientError::from(TransactionError::AccountNotFound);
+        assert!(matches!(
+            BridgeError::from_client_error(error, &instructions),
+            BridgeError::Rpc(_)
+        ));
+    }
+
+    #[test]
+    fn test_error_tables_are_complete() {
+        // Anchor numbers the variants of an `#[error_code]` enum from 6000 in declaration order
+        let mut code = 6000;
+        while let Some(error) = BridgeProgramError::bitvm_bridge(code) {
+            assert_eq!(error.code(), code);
+            code += 1;
+        }
+        assert_eq!((code - 6000) as usize, BITVM_BRIDGE_ERRORS.len());
+
+        let mut code = 6000;
+        while let Some(error) = BridgeProgramError::btc_light_client(code) {
+            assert_eq!(error.code(), code);
+            code += 1;
+        }
+        assert_eq!((code - 6000) as usize, BTC_LIGHT_CLIENT_ERRORS.len());
+    }
+}
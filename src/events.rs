@@ -4,15 +4,19 @@ use anchor_client::{
     solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature},
 };
 
-use anyhow::Result;
 use async_trait::async_trait;
 use solana_transaction_status::UiTransactionEncoding;
 use std::{str::FromStr, time::Duration};
 use tokio::time::sleep;
 
-use crate::{utils, TransactionEvent};
+use crate::{
+    error::{BridgeError, Result},
+    utils, TransactionEvent,
+};
 
 /// Event handler trait for processing bridge events
+///
+/// Handler errors stop the monitor and are surfaced as [`BridgeError::EventHandler`].
 #[async_trait]
 pub trait EventHandler: Send + Sync {
     async fn handle_mint(
@@ -22,7 +26,8 @@ pub trait EventHandler: Send + Sync {
         tx_signature: &str,
         to: &str,
         value: u64,
-    ) -> Result<()>;
+    ) -> anyhow::Result<()>;
+    #[allow(clippy::too_many_arguments)]
     async fn handle_burn(
         &self,
         tx_slot: u64,
@@ -33,7 +38,7 @@ pub trait EventHandler: Send + Sync {
         fee_rate: u32,
         value: u64,
         operator_id: u64,
-    ) -> Result<()>;
+    ) -> anyhow::Result<()>;
 }
 
 /// Monitor for bridge events
@@ -52,7 +57,7 @@ impl EventMonitor {
         handler: Box<dyn EventHandler>,
         last_signature: Option<String>,
         query_interval: u64,
-    ) -> anyhow::Result<Self> {
+    ) -> Result<Self> {
         let program_id = Pubkey::from_str(program_id)?;
         let rpc_client = RpcClient::new(rpc_url.to_string());
         let last_signature = if let Some(s) = last_signature {
@@ -103,7 +108,8 @@ impl EventMonitor {
                                             &mint_event.to,
                                             mint_event.value,
                                         )
-                                        .await?;
+                                        .await
+                                        .map_err(BridgeError::EventHandler)?;
                                 }
                                 TransactionEvent::Burn(burn_event) => {
                                     self.handler
@@ -117,7 +123,8 @@ impl EventMonitor {
                                             burn_event.value,
                                             burn_event.operator_id,
                                         )
-                                        .await?;
+                                        .await
+                                        .map_err(BridgeError::EventHandler)?;
                                 }
                             }
                         }
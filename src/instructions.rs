@@ -0,0 +1,268 @@
+//! Instruction builders for the BitVM bridge and BTC light client programs
+//!
+//! These functions are pure: they do not touch the network, so bridge instructions can be
+//! composed with other instructions, bundled or handed to external tooling. State that the
+//! programs expect to be passed in (such as the bridge mint) is taken from a [`BridgeState`]
+//! fetched by the caller, and the fixed state accounts are passed in so callers can derive them
+//! once (see [`pda::bridge_state`] and [`pda::btc_light_client_state`]).
+
+use anchor_client::{
+    anchor_lang::{InstructionData, ToAccountMetas},
+    solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_program},
+};
+use anchor_spl::{
+    associated_token::{get_associated_token_address, spl_associated_token_account},
+    token::spl_token,
+};
+use bitvm_bridge::state::BridgeState;
+use btc_light_client::instructions::verify_tx::BtcTxProof;
+
+use crate::pda;
+
+/// Build a `mint` instruction of the bridge program
+///
+/// The tx verified state account is only passed when the bridge requires verification.
+#[allow(clippy::too_many_arguments)]
+pub fn mint(
+    program_id: &Pubkey,
+    light_client_id: &Pubkey,
+    bridge_state_address: &Pubkey,
+    bridge_state: &BridgeState,
+    mint_authority: &Pubkey,
+    recipient: &Pubkey,
+    tx_id: [u8; 32],
+    amount: u64,
+) -> Instruction {
+    let (tx_minted_state, _) = pda::tx_minted_state(program_id, &tx_id);
+    let tx_verified_state = if bridge_state.skip_tx_verification {
+        None
+    } else {
+        Some(pda::tx_verified_state(light_client_id, &tx_id).0)
+    };
+
+    let accounts = bitvm_bridge::accounts::MintToken {
+        mint_authority: *mint_authority,
+        recipient: *recipient,
+        mint_account: bridge_state.mint_account,
+        associated_token_account: get_associated_token_address(
+            recipient,
+            &bridge_state.mint_account,
+        ),
+        token_program: spl_token::ID,
+        associated_token_program: spl_associated_token_account::ID,
+        system_program: system_program::ID,
+        bridge_state: *bridge_state_address,
+        tx_minted_state,
+        tx_verified_state,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts: accounts.to_account_metas(None),
+        data: bitvm_bridge::instruction::Mint { tx_id, amount }.data(),
+    }
+}
+
+/// Build a `burn` instruction of the bridge program, burning from the authority's token account
+#[allow(clippy::too_many_arguments)]
+pub fn burn(
+    program_id: &Pubkey,
+    bridge_state_address: &Pubkey,
+    bridge_state: &BridgeState,
+    authority: &Pubkey,
+    amount: u64,
+    btc_addr: &str,
+    fee_rate: u32,
+    operator_id: u64,
+) -> Instruction {
+    let accounts = bitvm_bridge::accounts::BurnToken {
+        authority: *authority,
+        mint_account: bridge_state.mint_account,
+        associated_token_account: get_associated_token_address(
+            authority,
+            &bridge_state.mint_account,
+        ),
+        token_program: spl_token::ID,
+        bridge_state: *bridge_state_address,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts: accounts.to_account_metas(None),
+        data: bitvm_bridge::instruction::Burn {
+            amount,
+            btc_addr: btc_addr.to_string(),
+            fee_rate,
+            operator_id,
+        }
+        .data(),
+    }
+}
+
+/// Build a `verify_transaction` instruction of the light client program
+pub fn verify_transaction(
+    light_client_id: &Pubkey,
+    light_client_state: &Pubkey,
+    payer: &Pubkey,
+    block_height: u64,
+    tx_proof: BtcTxProof,
+) -> Instruction {
+    let (block_hash_entry, _) = pda::block_hash_entry(light_client_id, block_height);
+    let (tx_verified_state, _) = pda::tx_verified_state(light_client_id, &tx_proof.tx_id);
+
+    let accounts = btc_light_client::accounts::VerifyTransaction {
+        state: *light_client_state,
+        tx_verified_state,
+        payer: *payer,
+        system_program: system_program::ID,
+        block_hash_entry,
+    };
+
+    Instruction {
+        program_id: *light_client_id,
+        accounts: accounts.to_account_metas(None),
+        data: btc_light_client::instruction::VerifyTransaction {
+            block_height,
+            tx_proof,
+        }
+        .data(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::bridge_state;
+    use anchor_client::anchor_lang::Discriminator;
+
+    #[test]
+    fn test_mint_instruction() {
+        let program_id = Pubkey::new_unique();
+        let light_client_id = Pubkey::new_unique();
+        let mint_authority = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let tx_id = [7; 32];
+
+        let (bridge_state_address, _) = pda::bridge_state(&program_id);
+        let state = bridge_state(false);
+        let ix = mint(
+            &program_id,
+            &light_client_id,
+            &bridge_state_address,
+            &state,
+            &mint_authority,
+            &recipient,
+            tx_id,
+            1_000,
+        );
+        assert_eq!(ix.program_id, program_id);
+        assert!(ix
+            .data
+            .starts_with(bitvm_bridge::instruction::Mint::DISCRIMINATOR));
+        assert_eq!(ix.accounts[0].pubkey, mint_authority);
+        assert!(ix.accounts[0].is_signer);
+        let (tx_verified_state, _) = pda::tx_verified_state(&light_client_id, &tx_id);
+        assert!(ix
+            .accounts
+            .iter()
+            .any(|meta| meta.pubkey == tx_verified_state));
+
+        let state = bridge_state(true);
+        let ix = mint(
+            &program_id,
+            &light_client_id,
+            &bridge_state_address,
+            &state,
+            &mint_authority,
+            &recipient,
+            tx_id,
+            1_000,
+        );
+        assert!(!ix
+            .accounts
+            .iter()
+            .any(|meta| meta.pubkey == tx_verified_state));
+    }
+
+    #[test]
+    fn test_burn_instruction() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (bridge_state_address, _) = pda::bridge_state(&program_id);
+        let state = bridge_state(false);
+
+        let ix = burn(
+            &program_id,
+            &bridge_state_address,
+            &state,
+            &authority,
+            5_000,
+            "bcrt1phcnl4zcl2fu047pv4wx6y058v8u0n02at6lthvm7pcf2wrvjm5tqatn90k",
+            10,
+            1,
+        );
+        assert_eq!(ix.program_id, program_id);
+        assert!(ix
+            .data
+            .starts_with(bitvm_bridge::instruction::Burn::DISCRIMINATOR));
+
+        let expected = [
+            (authority, true, true),
+            (state.mint_account, false, true),
+            (
+                get_associated_token_address(&authority, &state.mint_account),
+                false,
+                true,
+            ),
+            (spl_token::ID, false, false),
+            (bridge_state_address, false, false),
+        ];
+        let accounts: Vec<_> = ix
+            .accounts
+            .iter()
+            .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+            .collect();
+        assert_eq!(accounts, expected);
+    }
+
+    #[test]
+    fn test_verify_transaction_instruction() {
+        let light_client_id = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let tx_proof = BtcTxProof {
+            block_header: vec![0; 80],
+            tx_id: [3; 32],
+            tx_index: 0,
+            merkle_proof: vec![],
+            raw_tx: vec![],
+            output_index: 0,
+            expected_amount: 0,
+            expected_script_hash: [0; 32],
+        };
+
+        let (light_client_state, _) = pda::btc_light_client_state(&light_client_id);
+        let ix = verify_transaction(&light_client_id, &light_client_state, &payer, 100, tx_proof);
+        assert_eq!(ix.program_id, light_client_id);
+        assert!(ix
+            .data
+            .starts_with(btc_light_client::instruction::VerifyTransaction::DISCRIMINATOR));
+
+        let expected = [
+            (light_client_state, false, false),
+            (
+                pda::tx_verified_state(&light_client_id, &[3; 32]).0,
+                false,
+                true,
+            ),
+            (payer, true, true),
+            (system_program::ID, false, false),
+            (pda::block_hash_entry(&light_client_id, 100).0, false, false),
+        ];
+        let accounts: Vec<_> = ix
+            .accounts
+            .iter()
+            .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+            .collect();
+        assert_eq!(accounts, expected);
+    }
+}
@@ -1,11 +1,22 @@
 use serde::{Deserialize, Serialize};
 
 pub mod bridge_client;
+pub mod btc;
+pub mod compute_budget;
+pub mod error;
 pub mod events;
+pub mod instructions;
+pub mod pda;
+pub mod preflight;
 pub mod query_client;
+pub mod simulation;
+pub mod state;
 pub mod utils;
 
-#[derive(Debug)]
+#[cfg(test)]
+mod test_fixtures;
+
+#[derive(Debug, Clone)]
 pub enum TransactionEvent {
     Mint(MintEventData),
     Burn(BurnEventData),
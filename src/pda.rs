@@ -0,0 +1,42 @@
+//! Program derived addresses of the BitVM bridge and BTC light client accounts
+//!
+//! Every function returns the address together with its bump seed.
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+pub const BRIDGE_STATE_SEED: &[u8] = b"bridge_state";
+pub const TX_MINTED_STATE_SEED: &[u8] = b"tx_minted_state";
+pub const TX_VERIFIED_STATE_SEED: &[u8] = b"tx_verified_state";
+pub const BTC_LIGHT_CLIENT_SEED: &[u8] = b"btc_light_client";
+pub const BLOCK_HASH_ENTRY_SEED: &[u8] = b"block_hash_entry";
+
+/// Bridge configuration account of the bridge program
+pub fn bridge_state(bitvm_bridge_program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BRIDGE_STATE_SEED], bitvm_bridge_program_id)
+}
+
+/// Account marking a Bitcoin transaction as minted by the bridge program
+pub fn tx_minted_state(bitvm_bridge_program_id: &Pubkey, tx_id: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TX_MINTED_STATE_SEED, tx_id], bitvm_bridge_program_id)
+}
+
+/// Account marking a Bitcoin transaction as verified by the light client
+pub fn tx_verified_state(btc_light_client_program_id: &Pubkey, tx_id: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TX_VERIFIED_STATE_SEED, tx_id],
+        btc_light_client_program_id,
+    )
+}
+
+/// Global state account of the light client
+pub fn btc_light_client_state(btc_light_client_program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BTC_LIGHT_CLIENT_SEED], btc_light_client_program_id)
+}
+
+/// Block hash stored by the light client for a Bitcoin block height
+pub fn block_hash_entry(btc_light_client_program_id: &Pubkey, block_height: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[BLOCK_HASH_ENTRY_SEED, &block_height.to_le_bytes()],
+        btc_light_client_program_id,
+    )
+}
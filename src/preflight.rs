@@ -0,0 +1,231 @@
+//! Client-side checks run before bridge transactions are signed
+//!
+//! The checks mirror the on-chain constraints so a doomed transaction is rejected with a
+//! descriptive error instead of paying fees for an opaque program error.
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use bitcoin::Network;
+use thiserror::Error;
+
+use crate::{
+    btc::address::{parse_btc_address, BtcAddressError},
+    state::{BridgeStateSnapshot, TxMintedStateSnapshot, TxVerificationStatus},
+};
+
+/// Reason a mint was rejected before being sent
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MintRejection {
+    #[error("bitcoin transaction {} is already minted", hex::encode(.tx_id))]
+    AlreadyMinted { tx_id: [u8; 32] },
+
+    #[error("bitcoin transaction is not verified by the light client: {0:?}")]
+    NotVerified(TxVerificationStatus),
+
+    #[error("mint amount {amount} is outside the bridge limits {min}..={max}")]
+    AmountOutOfRange { amount: u64, min: u64, max: u64 },
+}
+
+/// Check a mint of `amount` against the bridge state and the records of its Bitcoin transaction
+pub fn check_mint(
+    bridge_state: &BridgeStateSnapshot,
+    tx_minted_state: Option<&TxMintedStateSnapshot>,
+    verification_status: TxVerificationStatus,
+    amount: u64,
+) -> Result<(), MintRejection> {
+    if let Some(state) = tx_minted_state.filter(|state| state.is_minted) {
+        return Err(MintRejection::AlreadyMinted { tx_id: state.tx_id });
+    }
+    if !verification_status.allows_mint() {
+        return Err(MintRejection::NotVerified(verification_status));
+    }
+    if !bridge_state.is_mint_amount_allowed(amount) {
+        return Err(MintRejection::AmountOutOfRange {
+            amount,
+            min: bridge_state.min_btc_per_mint,
+            max: bridge_state.max_btc_per_mint,
+        });
+    }
+    Ok(())
+}
+
+/// Reason a burn was rejected before being sent
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BurnRejection {
+    #[error("burns are paused by the bridge")]
+    BurnPaused,
+
+    #[error("token account {0} does not exist")]
+    TokenAccountNotFound(Pubkey),
+
+    #[error("token balance {balance} is lower than the burn amount {amount}")]
+    InsufficientBalance { balance: u64, amount: u64 },
+
+    #[error("burn amount {amount} is outside the bridge limits {min}..={max}")]
+    AmountOutOfRange { amount: u64, min: u64, max: u64 },
+
+    #[error("invalid bitcoin address {address}: {reason}")]
+    InvalidBtcAddress {
+        address: String,
+        reason: BtcAddressError,
+    },
+}
+
+/// Check a burn of `amount` from `token_account` holding `token_balance` (`None` if the
+/// account does not exist) against the bridge state and the burn address
+///
+/// `btc_addr` is checked against `btc_network` when the client has one configured. The
+/// operator id of the burn is not checked: the bridge state keeps no set of operators to check
+/// it against, the id is only recorded in the burn event.
+pub fn check_burn(
+    bridge_state: &BridgeStateSnapshot,
+    token_account: &Pubkey,
+    token_balance: Option<u64>,
+    amount: u64,
+    btc_addr: &str,
+    btc_network: Option<Network>,
+) -> Result<(), BurnRejection> {
+    if bridge_state.is_burn_paused() {
+        return Err(BurnRejection::BurnPaused);
+    }
+    let balance = token_balance.ok_or(BurnRejection::TokenAccountNotFound(*token_account))?;
+    if balance < amount {
+        return Err(BurnRejection::InsufficientBalance { balance, amount });
+    }
+    if !bridge_state.is_burn_amount_allowed(amount) {
+        return Err(BurnRejection::AmountOutOfRange {
+            amount,
+            min: bridge_state.min_btc_per_burn,
+            max: bridge_state.max_btc_per_burn,
+        });
+    }
+    parse_btc_address(btc_addr, btc_network).map_err(|reason| {
+        BurnRejection::InvalidBtcAddress {
+            address: btc_addr.to_string(),
+            reason,
+        }
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::bridge_state_snapshot;
+
+    #[test]
+    fn test_check_mint() {
+        let bridge_state = bridge_state_snapshot();
+        let minted = TxMintedStateSnapshot {
+            tx_id: [1; 32],
+            is_minted: true,
+        };
+
+        assert_eq!(
+            check_mint(&bridge_state, None, TxVerificationStatus::Verified, 5_000),
+            Ok(())
+        );
+        assert_eq!(
+            check_mint(
+                &bridge_state,
+                Some(&minted),
+                TxVerificationStatus::Verified,
+                5_000
+            ),
+            Err(MintRejection::AlreadyMinted { tx_id: [1; 32] })
+        );
+        assert_eq!(
+            check_mint(
+                &bridge_state,
+                None,
+                TxVerificationStatus::NotSubmitted,
+                5_000
+            ),
+            Err(MintRejection::NotVerified(
+                TxVerificationStatus::NotSubmitted
+            ))
+        );
+        assert_eq!(
+            check_mint(&bridge_state, None, TxVerificationStatus::Verified, 500),
+            Err(MintRejection::AmountOutOfRange {
+                amount: 500,
+                min: 1_000,
+                max: 100_000
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_burn() {
+        let mut bridge_state = bridge_state_snapshot();
+        let token_account = Pubkey::new_unique();
+        let btc_addr = "bcrt1phcnl4zcl2fu047pv4wx6y058v8u0n02at6lthvm7pcf2wrvjm5tqatn90k";
+
+        assert_eq!(
+            check_burn(
+                &bridge_state,
+                &token_account,
+                Some(300_000),
+                300_000,
+                btc_addr,
+                None,
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_burn(&bridge_state, &token_account, None, 300_000, btc_addr, None,),
+            Err(BurnRejection::TokenAccountNotFound(token_account))
+        );
+        assert_eq!(
+            check_burn(
+                &bridge_state,
+                &token_account,
+                Some(200_000),
+                300_000,
+                btc_addr,
+                None,
+            ),
+            Err(BurnRejection::InsufficientBalance {
+                balance: 200_000,
+                amount: 300_000
+            })
+        );
+        assert!(matches!(
+            check_burn(
+                &bridge_state,
+                &token_account,
+                Some(300_000),
+                300_000,
+                "not-an-address",
+                None,
+            ),
+            Err(BurnRejection::InvalidBtcAddress { .. })
+        ));
+        assert!(matches!(
+            check_burn(
+                &bridge_state,
+                &token_account,
+                Some(300_000),
+                300_000,
+                btc_addr,
+                Some(Network::Bitcoin),
+            ),
+            Err(BurnRejection::InvalidBtcAddress {
+                reason: BtcAddressError::WrongNetwork(_),
+                ..
+            })
+        ));
+
+        bridge_state.burn_paused = true;
+        assert_eq!(
+            check_burn(
+                &bridge_state,
+                &token_account,
+                Some(300_000),
+                300_000,
+                btc_addr,
+                None,
+            ),
+            Err(BurnRejection::BurnPaused)
+        );
+    }
+}
@@ -3,12 +3,10 @@ use anchor_client::{
     solana_sdk::{commitment_config::CommitmentConfig, signature::Signature},
 };
 
-use anyhow::Result;
-
 use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 
-use crate::{utils, TransactionEvent};
+use crate::{error::Result, utils, TransactionEvent};
 
 pub struct QueryClient {
     rpc_client: RpcClient,
@@ -17,20 +15,36 @@ pub struct QueryClient {
 impl QueryClient {
     pub fn new(url: String) -> Result<Self> {
         Ok(Self {
-            rpc_client: RpcClient::new(url),
+            rpc_client: RpcClient::new_with_commitment(url, CommitmentConfig::confirmed()),
+        })
+    }
+
+    pub fn new_with_timeout_and_commitment(
+        url: String,
+        timeout: Duration,
+        commitment: CommitmentConfig,
+    ) -> Result<Self> {
+        Ok(Self {
+            rpc_client: RpcClient::new_with_timeout_and_commitment(url, timeout, commitment),
         })
     }
 
     pub async fn get_transaction(
         &self,
         signature: &str,
-    ) -> anyhow::Result<EncodedConfirmedTransactionWithStatusMeta> {
+    ) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
         let signature = Signature::from_str(signature)?;
+        // `getTransaction` rejects `processed`, use at least `confirmed`
+        let commitment = if self.rpc_client.commitment().is_at_least_confirmed() {
+            self.rpc_client.commitment()
+        } else {
+            CommitmentConfig::confirmed()
+        };
         let tx = self.rpc_client.get_transaction_with_config(
             &signature,
             RpcTransactionConfig {
                 encoding: Some(UiTransactionEncoding::Json),
-                commitment: Some(CommitmentConfig::confirmed()),
+                commitment: Some(commitment),
                 max_supported_transaction_version: Some(0),
             },
         )?;
@@ -40,7 +54,7 @@ impl QueryClient {
     pub async fn parse_transaction_event(
         &self,
         signature: &str,
-    ) -> anyhow::Result<Option<TransactionEvent>> {
+    ) -> Result<Option<TransactionEvent>> {
         let tx = self.get_transaction(signature).await?;
         utils::parse_transaction_event(&tx)
     }
@@ -0,0 +1,46 @@
+use anchor_client::{
+    solana_client::rpc_response::RpcSimulateTransactionResult,
+    solana_sdk::transaction::TransactionError,
+};
+
+use crate::{error::ProgramError, utils, TransactionEvent};
+
+/// Outcome of a simulated bridge transaction
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    /// Error the transaction would fail with, `None` if it would succeed
+    pub err: Option<TransactionError>,
+    /// Program logs emitted during the simulation
+    pub logs: Vec<String>,
+    /// Compute units consumed by the whole transaction
+    pub units_consumed: Option<u64>,
+    /// Custom program error the transaction would fail with, decoded against the program ids
+    /// of the client that ran the simulation
+    pub program_error: Option<ProgramError>,
+    /// Mint and burn events decoded from the simulated logs
+    pub events: Vec<TransactionEvent>,
+}
+
+impl SimulationResult {
+    pub fn is_success(&self) -> bool {
+        self.err.is_none()
+    }
+}
+
+impl From<RpcSimulateTransactionResult> for SimulationResult {
+    fn from(result: RpcSimulateTransactionResult) -> Self {
+        let logs = result.logs.unwrap_or_default();
+        let events = utils::parse_log_events(&logs);
+        let program_error = result
+            .err
+            .as_ref()
+            .and_then(|err| ProgramError::from_transaction_error(err, &logs));
+        Self {
+            err: result.err,
+            logs,
+            units_consumed: result.units_consumed,
+            program_error,
+            events,
+        }
+    }
+}
@@ -0,0 +1,297 @@
+//! Serializable snapshots of the on-chain bridge and light client accounts
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use bitvm_bridge::state::{BridgeState, TxMintedState};
+use btc_light_client::state::{BlockHashEntry, BtcLightClientState, TxVerifiedState};
+use serde::{Deserialize, Serialize};
+
+use crate::btc::hash_to_hex;
+
+/// Snapshot of the bridge program's `BridgeState` account
+///
+/// Amounts are in satoshis.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeStateSnapshot {
+    #[serde(with = "pubkey_string")]
+    pub mint_account: Pubkey,
+    #[serde(with = "pubkey_string")]
+    pub owner: Pubkey,
+    pub max_btc_per_mint: u64,
+    pub min_btc_per_mint: u64,
+    pub max_btc_per_burn: u64,
+    pub min_btc_per_burn: u64,
+    pub skip_tx_verification: bool,
+    pub burn_paused: bool,
+}
+
+impl BridgeStateSnapshot {
+    /// Whether mints require the Bitcoin transaction to be verified by the light client
+    pub fn requires_tx_verification(&self) -> bool {
+        !self.skip_tx_verification
+    }
+
+    pub fn is_burn_paused(&self) -> bool {
+        self.burn_paused
+    }
+
+    /// Whether `amount` is within the per-mint limits
+    pub fn is_mint_amount_allowed(&self, amount: u64) -> bool {
+        (self.min_btc_per_mint..=self.max_btc_per_mint).contains(&amount)
+    }
+
+    /// Whether `amount` is within the per-burn limits
+    pub fn is_burn_amount_allowed(&self, amount: u64) -> bool {
+        (self.min_btc_per_burn..=self.max_btc_per_burn).contains(&amount)
+    }
+}
+
+impl From<BridgeState> for BridgeStateSnapshot {
+    fn from(state: BridgeState) -> Self {
+        Self {
+            mint_account: state.mint_account,
+            owner: state.owner,
+            max_btc_per_mint: state.max_btc_per_mint,
+            min_btc_per_mint: state.min_btc_per_mint,
+            max_btc_per_burn: state.max_btc_per_burn,
+            min_btc_per_burn: state.min_btc_per_burn,
+            skip_tx_verification: state.skip_tx_verification,
+            burn_paused: state.burn_paused,
+        }
+    }
+}
+
+/// Mint record of a Bitcoin transaction in the bridge program
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxMintedStateSnapshot {
+    /// Bitcoin transaction id (internal byte order)
+    #[serde(with = "hex_bytes")]
+    pub tx_id: [u8; 32],
+    pub is_minted: bool,
+}
+
+impl TxMintedStateSnapshot {
+    pub fn new(tx_id: [u8; 32], state: TxMintedState) -> Self {
+        Self {
+            tx_id,
+            is_minted: state.is_minted,
+        }
+    }
+}
+
+/// Verification status of a Bitcoin transaction as seen by the bridge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxVerificationStatus {
+    /// No proof was submitted to the light client yet
+    NotSubmitted,
+    /// A verification record exists but is not marked verified
+    Unverified,
+    /// The light client verified the transaction
+    Verified,
+    /// The bridge skips verification, mints do not need a proof
+    VerificationSkippedByBridge,
+}
+
+impl TxVerificationStatus {
+    /// Status from the bridge skip flag and the light client's verification record
+    pub fn new(skip_tx_verification: bool, tx_verified_state: Option<&TxVerifiedState>) -> Self {
+        if skip_tx_verification {
+            return TxVerificationStatus::VerificationSkippedByBridge;
+        }
+        match tx_verified_state {
+            None => TxVerificationStatus::NotSubmitted,
+            Some(state) if state.is_verified => TxVerificationStatus::Verified,
+            Some(_) => TxVerificationStatus::Unverified,
+        }
+    }
+
+    /// Whether the bridge would accept a mint for the transaction
+    pub fn allows_mint(&self) -> bool {
+        matches!(
+            self,
+            TxVerificationStatus::Verified | TxVerificationStatus::VerificationSkippedByBridge
+        )
+    }
+}
+
+/// Snapshot of the light client's `BtcLightClientState` account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BtcLightClientStateSnapshot {
+    /// Height of the light client tip
+    pub latest_block_height: u64,
+    /// Hash of the tip as stored on-chain (internal byte order)
+    #[serde(with = "hex_bytes")]
+    pub latest_block_hash: [u8; 32],
+    /// Timestamp of the tip block header
+    pub latest_block_time: u32,
+    /// Confirmations required before a transaction can be verified
+    pub min_confirmations: u64,
+}
+
+impl BtcLightClientStateSnapshot {
+    /// Tip hash as hex, see [`hash_to_hex`]
+    pub fn latest_block_hash_hex(&self) -> String {
+        hash_to_hex(&self.latest_block_hash)
+    }
+
+    /// Number of confirmations of the block at `block_height`, counting the tip as one
+    pub fn confirmations(&self, block_height: u64) -> u64 {
+        if block_height > self.latest_block_height {
+            return 0;
+        }
+        self.latest_block_height - block_height + 1
+    }
+
+    /// Whether the block at `block_height` has at least `min_confirmations`
+    pub fn is_sufficiently_confirmed(&self, block_height: u64) -> bool {
+        self.confirmations(block_height) >= self.min_confirmations
+    }
+}
+
+impl From<BtcLightClientState> for BtcLightClientStateSnapshot {
+    fn from(state: BtcLightClientState) -> Self {
+        Self {
+            latest_block_height: state.latest_block_height,
+            latest_block_hash: state.latest_block_hash,
+            latest_block_time: state.latest_block_time,
+            min_confirmations: state.min_confirmations,
+        }
+    }
+}
+
+/// Block hash stored by the light client for a Bitcoin block height
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockHashEntrySnapshot {
+    pub height: u64,
+    /// Block hash as stored on-chain (internal byte order)
+    #[serde(with = "hex_bytes")]
+    pub hash: [u8; 32],
+}
+
+impl BlockHashEntrySnapshot {
+    /// Block hash as hex, see [`hash_to_hex`]
+    pub fn hash_hex(&self) -> String {
+        hash_to_hex(&self.hash)
+    }
+}
+
+impl From<BlockHashEntry> for BlockHashEntrySnapshot {
+    fn from(entry: BlockHashEntry) -> Self {
+        Self {
+            height: entry.height,
+            hash: entry.hash,
+        }
+    }
+}
+
+/// Serialize pubkeys as base58 strings
+mod pubkey_string {
+    use anchor_client::solana_sdk::pubkey::Pubkey;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(pubkey)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let pubkey = String::deserialize(deserializer)?;
+        Pubkey::from_str(&pubkey).map_err(D::Error::custom)
+    }
+}
+
+/// Serialize 32 byte hashes as hex strings
+mod hex_bytes {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let bytes = hex::decode(String::deserialize(deserializer)?).map_err(D::Error::custom)?;
+        bytes
+            .try_into()
+            .map_err(|_| D::Error::custom("expected 32 bytes"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::bridge_state_snapshot;
+
+    #[test]
+    fn test_bridge_state_snapshot() {
+        let snapshot = bridge_state_snapshot();
+        assert!(snapshot.requires_tx_verification());
+        assert!(snapshot.is_mint_amount_allowed(100_000));
+        assert!(!snapshot.is_mint_amount_allowed(999));
+        assert!(!snapshot.is_burn_amount_allowed(500_001));
+
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["mint_account"], snapshot.mint_account.to_string());
+        let decoded: BridgeStateSnapshot = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, snapshot);
+    }
+
+    #[test]
+    fn test_light_client_state_snapshot() {
+        let mut latest_block_hash = [0; 32];
+        latest_block_hash[31] = 0xab;
+        let snapshot = BtcLightClientStateSnapshot {
+            latest_block_height: 100,
+            latest_block_hash,
+            latest_block_time: 1_700_000_000,
+            min_confirmations: 6,
+        };
+        assert!(snapshot.latest_block_hash_hex().starts_with("ab00"));
+        assert_eq!(snapshot.confirmations(100), 1);
+        assert_eq!(snapshot.confirmations(95), 6);
+        assert_eq!(snapshot.confirmations(101), 0);
+        assert!(snapshot.is_sufficiently_confirmed(95));
+        assert!(!snapshot.is_sufficiently_confirmed(96));
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let decoded: BtcLightClientStateSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, snapshot);
+    }
+
+    #[test]
+    fn test_block_hash_entry_snapshot() {
+        let mut hash = [0; 32];
+        hash[0] = 0x01;
+        hash[31] = 0xff;
+        let entry = BlockHashEntrySnapshot { height: 42, hash };
+        assert!(entry.hash_hex().starts_with("ff00"));
+        assert!(entry.hash_hex().ends_with("0001"));
+
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["hash"], hex::encode(hash));
+        let decoded: BlockHashEntrySnapshot = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, entry);
+    }
+
+    #[test]
+    fn test_tx_verification_status() {
+        let verified = TxVerifiedState { is_verified: true };
+        let unverified = TxVerifiedState { is_verified: false };
+        assert_eq!(
+            TxVerificationStatus::new(false, None),
+            TxVerificationStatus::NotSubmitted
+        );
+        assert_eq!(
+            TxVerificationStatus::new(false, Some(&unverified)),
+            TxVerificationStatus::Unverified
+        );
+        assert_eq!(
+            TxVerificationStatus::new(false, Some(&verified)),
+            TxVerificationStatus::Verified
+        );
+        assert_eq!(
+            TxVerificationStatus::new(true, None),
+            TxVerificationStatus::VerificationSkippedByBridge
+        );
+        assert!(TxVerificationStatus::VerificationSkippedByBridge.allows_mint());
+        assert!(!TxVerificationStatus::Unverified.allows_mint());
+    }
+}
@@ -0,0 +1,55 @@
+//! Fixtures shared by the unit tests
+//!
+//! The Bitcoin fixtures are the block header, transaction and merkle proof of the
+//! verify_transaction example.
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use bitvm_bridge::state::BridgeState;
+
+use crate::state::BridgeStateSnapshot;
+
+pub const BLOCK_HEADER: &str = "00000020ac4051ad1135646ec3b65ab93bfcb42623f10f2861181b46043c0000000000002040291045b1a55e6db13db7dbd8c3147bf2d8f583b5381b0293bd62c7532f632666c067ffff001d266f87e3";
+pub const RAW_TX: &str = "020000000196a29590d526af588a13025d69cd9549b9c4712e212c7e51231166936bc0717c0100000000ffffffff0220a10700000000002200204ee81665c8c767c0dfe02e6d7c1b446ab01e84dc29b4c3634bc5daae24b52cbdca906d3500000000225120418ac1703f758fe750fecd897aac19c65cf41aeb58520b564316b3c02051305b00000000";
+pub const TX_ID: &str = "444fc273af34a87424a4b72c10b6393b0353505049128c5cb383da0358ab0b58";
+pub const TX_INDEX: u32 = 7;
+pub const MERKLE_PROOF: &str = "7c71c06b93661123517e2c212e71c4b94995cd695d02138a58af26d59095a29639e334b03828f792713b73abf3d3ed0c7202224e390f8d2f6b93f22dca85ea0b81c84af181d04680ade41b5dc3006876a3bf9fe4bf21193ffef1f0124e461ecd5c796b6c9707e804882e136418bcce72f005f59f8fa1bea691bf3738e77ab27830ecfe6fab2e947c9e452b53fc29773bb6e2f9d2c68c494b3d8f34ecd1b3dde4cf735b17a31639e35b885201a60d955aa1a83b057ba5fc59e6c5c27d2f90752da6c10f67250e44003cf0f554fa71cfa5e1aa4a6c1b676f422474dfd185afe378d2efbbda3e25ff85145d37b6fb3c51cfc9e3aedeca7ffe87e500cff2d55944acf1fb3c918c3fb6ced073e824323d02c78533780f15c49ce124dcc69f31b7260b2fd97ba6b3aed1a550db3e5418804fb8ce812b23e10fed70a65c29907c1ec9f7108d669f898dc0926043d8314d199e89f1113463e57997b7fe86212dbb735935112d761bb89f9e69ecd0d1471032d964847fc3a0318d07ce36f09e6537a3011e";
+
+/// Peg-in output 0 of [`RAW_TX`], a P2WSH output of 500000 sats
+pub const OUTPUT_AMOUNT: u64 = 500_000;
+pub const OUTPUT_SCRIPT_PUBKEY: &str =
+    "00204ee81665c8c767c0dfe02e6d7c1b446ab01e84dc29b4c3634bc5daae24b52cbd";
+pub const OUTPUT_SCRIPT_HASH: &str =
+    "4ee81665c8c767c0dfe02e6d7c1b446ab01e84dc29b4c3634bc5daae24b52cbd";
+
+pub fn output_script_hash() -> [u8; 32] {
+    hex::decode(OUTPUT_SCRIPT_HASH).unwrap().try_into().unwrap()
+}
+
+/// Bridge state with mint limits 1000..=100000 and burn limits 1000..=500000
+pub fn bridge_state_snapshot() -> BridgeStateSnapshot {
+    BridgeStateSnapshot {
+        mint_account: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        max_btc_per_mint: 100_000,
+        min_btc_per_mint: 1_000,
+        max_btc_per_burn: 500_000,
+        min_btc_per_burn: 1_000,
+        skip_tx_verification: false,
+        burn_paused: false,
+    }
+}
+
+/// Bridge state account with the limits of [`bridge_state_snapshot`]
+pub fn bridge_state(skip_tx_verification: bool) -> BridgeState {
+    let snapshot = bridge_state_snapshot();
+    BridgeState {
+        mint_account: snapshot.mint_account,
+        owner: snapshot.owner,
+        max_btc_per_mint: snapshot.max_btc_per_mint,
+        min_btc_per_mint: snapshot.min_btc_per_mint,
+        max_btc_per_burn: snapshot.max_btc_per_burn,
+        min_btc_per_burn: snapshot.min_btc_per_burn,
+        skip_tx_verification,
+        burn_paused: snapshot.burn_paused,
+    }
+}
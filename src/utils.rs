@@ -1,47 +1,98 @@
 use anchor_client::anchor_lang::{AnchorDeserialize, Discriminator};
-use anyhow::Result;
 use base64::{engine::general_purpose, Engine};
 use bitvm_bridge::events::{BurnEvent, MintEvent};
 use solana_transaction_status::{
     option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
 };
 
-use crate::{BurnEventData, MintEventData, TransactionEvent};
+use crate::{error::Result, BurnEventData, MintEventData, TransactionEvent};
 
 pub fn parse_transaction_event(
     tx: &EncodedConfirmedTransactionWithStatusMeta,
 ) -> Result<Option<TransactionEvent>> {
     if let Some(meta) = &tx.transaction.meta {
         if let OptionSerializer::Some(logs) = &meta.log_messages {
-            let mint_discriminator = MintEvent::DISCRIMINATOR;
-            let burn_discriminator = BurnEvent::DISCRIMINATOR;
-
-            for log in logs {
-                if let Some(data) = log.strip_prefix("Program data: ") {
-                    if let Ok(decoded) = general_purpose::STANDARD.decode(data) {
-                        if decoded.starts_with(mint_discriminator) {
-                            if let Ok(event) = MintEvent::try_from_slice(&decoded[8..]) {
-                                return Ok(Some(TransactionEvent::Mint(MintEventData {
-                                    to: event.to.to_string(),
-                                    value: event.value,
-                                })));
-                            }
-                        } else if decoded.starts_with(burn_discriminator) {
-                            if let Ok(event) = BurnEvent::try_from_slice(&decoded[8..]) {
-                                return Ok(Some(TransactionEvent::Burn(BurnEventData {
-                                    from: event.from.to_string(),
-                                    btc_addr: event.btc_addr,
-                                    fee_rate: event.fee_rate,
-                                    value: event.value,
-                                    operator_id: event.operator_id,
-                                })));
-                            }
-                        }
-                    }
-                }
-            }
+            return Ok(parse_log_events(logs).into_iter().next());
         }
     }
 
     Ok(None)
 }
+
+/// Decode every mint and burn event emitted in `logs`, in emission order
+pub fn parse_log_events(logs: &[String]) -> Vec<TransactionEvent> {
+    logs.iter().filter_map(|log| parse_log_event(log)).collect()
+}
+
+fn parse_log_event(log: &str) -> Option<TransactionEvent> {
+    let data = log.strip_prefix("Program data: ")?;
+    let decoded = general_purpose::STANDARD.decode(data).ok()?;
+
+    if decoded.starts_with(MintEvent::DISCRIMINATOR) {
+        let event = MintEvent::try_from_slice(&decoded[8..]).ok()?;
+        Some(TransactionEvent::Mint(MintEventData {
+            to: event.to.to_string(),
+            value: event.value,
+        }))
+    } else if decoded.starts_with(BurnEvent::DISCRIMINATOR) {
+        let event = BurnEvent::try_from_slice(&decoded[8..]).ok()?;
+        Some(TransactionEvent::Burn(BurnEventData {
+            from: event.from.to_string(),
+            btc_addr: event.btc_addr,
+            fee_rate: event.fee_rate,
+            value: event.value,
+            operator_id: event.operator_id,
+        }))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::{anchor_lang::AnchorSerialize, solana_sdk::pubkey::Pubkey};
+
+    fn program_data_log(discriminator: &[u8], event: impl AnchorSerialize) -> String {
+        let mut data = discriminator.to_vec();
+        data.extend(event.try_to_vec().unwrap());
+        format!("Program data: {}", general_purpose::STANDARD.encode(data))
+    }
+
+    #[test]
+    fn test_parse_log_events() {
+        let to = Pubkey::new_unique();
+        let logs = vec![
+            "Program log: Instruction: Mint".to_string(),
+            program_data_log(MintEvent::DISCRIMINATOR, MintEvent { to, value: 300000 }),
+            "Program data: not-base64".to_string(),
+            program_data_log(
+                BurnEvent::DISCRIMINATOR,
+                BurnEvent {
+                    from: to,
+                    btc_addr: "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string(),
+                    fee_rate: 10,
+                    value: 1000,
+                    operator_id: 1,
+                },
+            ),
+        ];
+
+        let events = parse_log_events(&logs);
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            TransactionEvent::Mint(mint) => {
+                assert_eq!(mint.to, to.to_string());
+                assert_eq!(mint.value, 300000);
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+        match &events[1] {
+            TransactionEvent::Burn(burn) => {
+                assert_eq!(burn.fee_rate, 10);
+                assert_eq!(burn.operator_id, 1);
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+}